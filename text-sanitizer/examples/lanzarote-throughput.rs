@@ -0,0 +1,65 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage examples/lanzarote-throughput.rs
+
+* This example measures the throughput of the sanitizer on the Lanzarote HTML page
+* which is mostly plain ASCII with some german umlauts
+*
+* It is run with:
+*   cargo run --release -p text-sanitizer --example lanzarote-throughput [ROUNDS]
+*
+*---------------------------------
+* Requirements:
+* - The test file "tests/data/lanzarote-com_de-ausfluge.html" must be present
+*/
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use text_sanitizer::TextSanitizer;
+
+fn main() {
+    let irounds: u32 = env::args()
+        .nth(1)
+        .and_then(|sarg| sarg.parse().ok())
+        .unwrap_or(2000);
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/data")
+        .join("lanzarote-com_de-ausfluge.html");
+    let vtext = fs::read(&path).expect("test file not readable");
+
+    let mut sanitizer = TextSanitizer::new_with_options(true, false, false);
+
+    sanitizer.add_request_language(&"en");
+    sanitizer.add_request_language(&"de");
+
+    //Warm up the Caches once
+    let mut ioutput = sanitizer.sanitize_u8(&vtext).len();
+
+    let start = Instant::now();
+
+    for _ in 0..irounds {
+        ioutput += sanitizer.sanitize_u8(&vtext).len();
+    }
+
+    let elapsed = start.elapsed();
+    let ibytes = vtext.len() as u64 * u64::from(irounds);
+    let dseconds = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9;
+
+    println!(
+        "file: '{}' ({} bytes); rounds: {}; output: {} bytes",
+        path.display(),
+        vtext.len(),
+        irounds,
+        ioutput
+    );
+    println!(
+        "time: {:.3} s; throughput: {:.1} MB/s",
+        dseconds,
+        ibytes as f64 / dseconds / 1e6
+    );
+}
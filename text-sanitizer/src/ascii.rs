@@ -0,0 +1,246 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage ascii.rs
+
+* This module implements the fast scanning of plain ASCII runs
+*
+*---------------------------------
+* Requirements:
+* - The SSE2 path needs an x86 / x86_64 target with the "sse2" target feature
+*   otherwise the portable word-at-a-time scan is used
+*/
+
+use core::mem;
+
+//==============================================================================
+// Constants
+
+const WORD_SIZE: usize = mem::size_of::<usize>();
+const LOW_BYTES: usize = !0 / 255;
+const HIGH_BYTES: usize = LOW_BYTES * 128;
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Checks whether a single byte is a plain ASCII character which is copied
+/// to the output unchanged.\
+/// These are the printable characters `32..127`, the newline and the tab.
+#[inline]
+pub fn is_plain_ascii(uc: u8) -> bool {
+    (32..127).contains(&uc) || uc == 10 || uc == 9
+}
+
+/// Finds the end of the run of plain ASCII characters starting at `start`.\
+/// The returned index is the position of the first byte that needs attention
+/// or the length of `text` when the run reaches the end.
+#[inline]
+pub fn scan_plain_ascii(text: &[u8], start: usize) -> usize {
+    scan_plain_ascii_impl(text, start)
+}
+
+/// Finds the end of the run of bytes needing attention starting at `start`.\
+/// The returned index is the position of the next plain ASCII character
+/// or the length of `text` when the run reaches the end.
+#[inline]
+pub fn scan_special(text: &[u8], start: usize) -> usize {
+    let mut ipos = start;
+
+    while ipos < text.len() && !is_plain_ascii(text[ipos]) {
+        ipos += 1;
+    }
+
+    ipos
+}
+
+/// Finds a safe position to split `text` at or after `start`.\
+/// The split is done right after the next newline within `window` bytes.
+/// Otherwise it is done before the next plain ASCII character which can never
+/// be part of a multi-byte sequence.\
+/// The returned index is the length of `text` when no safe position is found.
+#[allow(dead_code)]
+pub fn find_safe_boundary(text: &[u8], start: usize, window: usize) -> usize {
+    let itxtlen = text.len();
+    let iwndend = if start + window < itxtlen {
+        start + window
+    } else {
+        itxtlen
+    };
+
+    if start >= itxtlen {
+        return itxtlen;
+    }
+
+    if let Some(inl) = text[start..iwndend].iter().position(|uc| *uc == 10) {
+        return start + inl + 1;
+    }
+
+    let mut ipos = start;
+
+    while ipos < itxtlen && !is_plain_ascii(text[ipos]) {
+        ipos += 1;
+    }
+
+    ipos
+}
+
+#[inline]
+fn read_word(text: &[u8], start: usize) -> usize {
+    let mut word = [0u8; WORD_SIZE];
+
+    word.copy_from_slice(&text[start..(start + WORD_SIZE)]);
+
+    usize::from_ne_bytes(word)
+}
+
+/// Checks a whole word of bytes at once.\
+/// A `true` result guarantees that all bytes are printable ASCII characters.
+/// Newlines and tabs make the check fail and must be confirmed byte by byte.
+#[inline]
+fn is_printable_word(word: usize) -> bool {
+    //Any Byte with the high bit set
+    let high = word & HIGH_BYTES;
+    //Any Byte below 0x20
+    let control = word.wrapping_sub(LOW_BYTES * 0x20) & !word & HIGH_BYTES;
+    //Any Byte equal to 0x7f
+    let delete = word ^ (LOW_BYTES * 0x7f);
+    let delete = delete.wrapping_sub(LOW_BYTES) & !delete & HIGH_BYTES;
+
+    (high | control | delete) == 0
+}
+
+#[allow(dead_code)]
+fn scan_plain_ascii_portable(text: &[u8], start: usize) -> usize {
+    let itxtlen = text.len();
+    let mut ipos = start;
+
+    loop {
+        while ipos + WORD_SIZE <= itxtlen && is_printable_word(read_word(text, ipos)) {
+            ipos += WORD_SIZE;
+        }
+
+        //Confirm the next Word byte by byte
+        let iwrdend = if ipos + WORD_SIZE < itxtlen {
+            ipos + WORD_SIZE
+        } else {
+            itxtlen
+        };
+
+        while ipos < iwrdend && is_plain_ascii(text[ipos]) {
+            ipos += 1;
+        }
+
+        if ipos < iwrdend || ipos >= itxtlen {
+            break;
+        }
+    } //loop
+
+    ipos
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+fn scan_plain_ascii_impl(text: &[u8], start: usize) -> usize {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    const BLOCK_SIZE: usize = 16;
+
+    let itxtlen = text.len();
+    let mut ipos = start;
+
+    unsafe {
+        let space = _mm_set1_epi8(0x20);
+        let delete = _mm_set1_epi8(0x7f);
+        let newline = _mm_set1_epi8(0x0a);
+        let tab = _mm_set1_epi8(0x09);
+
+        while ipos + BLOCK_SIZE <= itxtlen {
+            let block = _mm_loadu_si128(text.as_ptr().add(ipos) as *const __m128i);
+
+            //Signed comparison also catches all Bytes from 0x80 to 0xff
+            let special = _mm_or_si128(_mm_cmplt_epi8(block, space), _mm_cmpeq_epi8(block, delete));
+            let allowed = _mm_or_si128(_mm_cmpeq_epi8(block, newline), _mm_cmpeq_epi8(block, tab));
+            let mask = _mm_movemask_epi8(_mm_andnot_si128(allowed, special));
+
+            if mask != 0 {
+                return ipos + mask.trailing_zeros() as usize;
+            }
+
+            ipos += BLOCK_SIZE;
+        } //while ipos + BLOCK_SIZE <= itxtlen
+    }
+
+    scan_plain_ascii_portable(text, ipos)
+}
+
+#[cfg(not(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+fn scan_plain_ascii_impl(text: &[u8], start: usize) -> usize {
+    scan_plain_ascii_portable(text, start)
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn scan_all_bytes() {
+    //-------------------------------------
+    // Every single byte value at every position of a long ASCII text
+
+    let base: Vec<u8> = b"Sichern Sie sich Ihren Platz bei einem dieser Ausfluege.\n".to_vec();
+
+    for ipos in 0..base.len() {
+        for uc in 0..=255u8 {
+            let mut text = base.clone();
+
+            text[ipos] = uc;
+
+            let iexpected = if is_plain_ascii(uc) { base.len() } else { ipos };
+
+            assert_eq!(scan_plain_ascii(&text, 0), iexpected);
+            assert_eq!(scan_plain_ascii_portable(&text, 0), iexpected);
+        }
+    }
+}
+
+#[test]
+fn scan_special_run() {
+    let text = "Ausfl\u{fc}ge \u{2764}\u{1f496}\tok\r\n".as_bytes();
+
+    let iascend = scan_plain_ascii(text, 0);
+
+    assert_eq!(iascend, 5);
+    assert_eq!(scan_special(text, iascend), 7);
+
+    let iascend = scan_plain_ascii(text, 7);
+
+    assert_eq!(iascend, 10);
+    assert_eq!(scan_special(text, iascend), 17);
+    assert_eq!(scan_plain_ascii(text, 17), 20);
+    assert_eq!(scan_special(text, 20), 21);
+    assert_eq!(scan_plain_ascii(text, 21), text.len());
+}
+
+#[test]
+fn safe_boundary() {
+    let text = "Ausfl\u{fc}ge\nbuchen \u{2764}\u{2764}!".as_bytes();
+
+    //The next Newline within the Window
+    assert_eq!(find_safe_boundary(text, 0, 64), 10);
+    assert_eq!(find_safe_boundary(text, 2, 64), 10);
+    //No Newline within the Window
+    assert_eq!(find_safe_boundary(text, 2, 4), 2);
+    assert_eq!(find_safe_boundary(text, 5, 2), 7);
+    //Never inside a multi-byte Sequence
+    assert_eq!(find_safe_boundary(text, 17, 2), 23);
+    assert_eq!(find_safe_boundary(text, 22, 64), 23);
+    assert_eq!(find_safe_boundary(text, 24, 64), text.len());
+}
@@ -0,0 +1,371 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage csv.rs
+
+* This module implements the CSV-aware and TSV-aware sanitizing
+* It parses the records and sanitizes each field on its own
+* The delimiters, quotes and line endings of the records are kept
+*
+*---------------------------------
+* Requirements:
+* - no further Rust crates
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str;
+
+use crate::ascii::is_plain_ascii;
+
+//==============================================================================
+// Structure CsvOptions Declaration
+
+/// Describes the format of CSV or TSV data and which columns are sanitized.
+///
+/// # Example:
+///
+/// Sanitize only the second and the third column of TSV data
+/// ```
+///    use text_sanitizer::CsvOptions;
+///
+///    let options = CsvOptions {
+///        columns: vec![1, 2],
+///        ..CsvOptions::tsv()
+///    };
+///
+///    assert_eq!(options.delimiter, b'\t');
+///    assert!(!options.is_column_selected(0));
+///    assert!(options.is_column_selected(2));
+/// ```
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvOptions {
+    /// separator of the fields like b',' or b'\t'.
+    pub delimiter: u8,
+    /// character which encloses fields with special characters.
+    pub quote: u8,
+    /// indices of the sanitized columns starting at 0. All columns are sanitized when empty.\
+    /// The fields of the other columns are kept as in the source. Only their invalid bytes
+    /// are replaced like in the sanitized columns because the output is valid UTF-8.
+    pub columns: Vec<usize>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions::csv()
+    }
+}
+
+impl CsvOptions {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Options for comma separated values.
+    pub fn csv() -> CsvOptions {
+        CsvOptions {
+            delimiter: b',',
+            quote: b'"',
+            columns: Vec::new(),
+        }
+    }
+
+    /// Options for tab separated values.
+    pub fn tsv() -> CsvOptions {
+        CsvOptions {
+            delimiter: b'\t',
+            ..CsvOptions::csv()
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Checks whether the column with the index `icolumn` is sanitized.
+    pub fn is_column_selected(&self, icolumn: usize) -> bool {
+        self.columns.is_empty() || self.columns.contains(&icolumn)
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Finds the end of the field starting at `start`.\
+/// Returns the end of the field and whether it was quoted.
+fn find_field_end(text: &[u8], start: usize, options: &CsvOptions) -> (usize, bool) {
+    let mut ipos = start;
+    let bquoted = text.get(start) == Some(&options.quote);
+
+    if bquoted {
+        ipos += 1;
+
+        while ipos < text.len() {
+            if text[ipos] == options.quote {
+                if text.get(ipos + 1) == Some(&options.quote) {
+                    //Escaped Quote
+                    ipos += 2;
+                } else {
+                    ipos += 1;
+                    break;
+                }
+            } else {
+                ipos += 1;
+            }
+        } //while ipos < text.len()
+    } //if bquoted
+
+    //Unquoted Fields and Data after the closing Quote end at the Delimiter or the Line End
+    while ipos < text.len()
+        && text[ipos] != options.delimiter
+        && text[ipos] != b'\n'
+        && !(text[ipos] == b'\r' && text.get(ipos + 1) == Some(&b'\n'))
+    {
+        ipos += 1;
+    }
+
+    (ipos, bquoted)
+}
+
+/// Removes the enclosing quotes of a field and unescapes the doubled quotes.
+fn unquote_field(field: &[u8], options: &CsvOptions) -> Vec<u8> {
+    let mut vcontent = Vec::with_capacity(field.len());
+    let mut ipos = 1;
+    let mut bquoted = true;
+
+    while ipos < field.len() {
+        let uc = field[ipos];
+
+        if bquoted && uc == options.quote {
+            if field.get(ipos + 1) == Some(&options.quote) {
+                vcontent.push(uc);
+                ipos += 1;
+            } else {
+                bquoted = false;
+            }
+        } else {
+            vcontent.push(uc);
+        }
+
+        ipos += 1;
+    } //while ipos < field.len()
+
+    vcontent
+}
+
+/// Adds the sanitized `run` to the field.\
+/// Replacements never add delimiters or line breaks. These become spaces.
+fn push_sanitized<F>(run: &[u8], sfield: &mut String, options: &CsvOptions, sanitize: &F)
+where
+    F: Fn(&[u8]) -> String,
+{
+    for c in sanitize(run).chars() {
+        if c == '\n' || c == '\r' || c == options.delimiter as char {
+            sfield.push(' ');
+        } else {
+            sfield.push(c);
+        }
+    }
+}
+
+/// Adds a field of a column which is not selected as in the source.\
+/// Only its invalid bytes are given to `sanitize`.
+fn push_source_field<F>(field: &[u8], srstxt: &mut String, options: &CsvOptions, sanitize: &F)
+where
+    F: Fn(&[u8]) -> String,
+{
+    let mut ic: usize = 0;
+
+    while ic < field.len() {
+        match str::from_utf8(&field[ic..]) {
+            Ok(svalid) => {
+                srstxt.push_str(svalid);
+                ic = field.len();
+            }
+            Err(e) => {
+                let ivalid = ic + e.valid_up_to();
+                let iinvalid = ivalid + e.error_len().unwrap_or(field.len() - ivalid);
+
+                //The valid Part was checked already
+                if let Ok(svalid) = str::from_utf8(&field[ic..ivalid]) {
+                    srstxt.push_str(svalid);
+                }
+
+                push_sanitized(&field[ivalid..iinvalid], srstxt, options, sanitize);
+
+                ic = iinvalid;
+            }
+        } //match str::from_utf8(&field[ic..])
+    } //while ic < field.len()
+}
+
+/// Sanitizes the content of a field.\
+/// Replacements never add delimiters or line breaks. These become spaces.
+fn sanitize_field<F>(content: &[u8], options: &CsvOptions, sanitize: &F) -> String
+where
+    F: Fn(&[u8]) -> String,
+{
+    let mut sfield = String::with_capacity(content.len());
+    let mut ic: usize = 0;
+
+    while ic < content.len() {
+        if is_plain_ascii(content[ic]) {
+            sfield.push(content[ic] as char);
+            ic += 1;
+        } else {
+            let mut iend = ic + 1;
+
+            while iend < content.len() && !is_plain_ascii(content[iend]) {
+                iend += 1;
+            }
+
+            push_sanitized(&content[ic..iend], &mut sfield, options, sanitize);
+
+            ic = iend;
+        } //if is_plain_ascii(content[ic])
+    } //while ic < content.len()
+
+    sfield
+}
+
+/// Adds the sanitized field and quotes it when it was quoted or needs quotes now.
+fn push_field(sfield: &str, bquoted: bool, srstxt: &mut String, options: &CsvOptions) {
+    let cquote = options.quote as char;
+    let bquote = bquoted
+        || sfield
+            .chars()
+            .any(|c| c == cquote || c == options.delimiter as char || c == '\n' || c == '\r');
+
+    if bquote {
+        srstxt.push(cquote);
+
+        for c in sfield.chars() {
+            if c == cquote {
+                srstxt.push(cquote);
+            }
+
+            srstxt.push(c);
+        }
+
+        srstxt.push(cquote);
+    } else {
+        srstxt.push_str(sfield);
+    }
+}
+
+/// Sanitizes CSV or TSV data field by field.\
+/// The content of the quoted fields is unquoted before `sanitize` replaces it.
+/// The sanitized fields are quoted again when they were quoted or contain special
+/// characters now. Fields of columns which are not selected are kept as in the source
+/// except for their invalid bytes.
+pub fn sanitize_csv<F>(text: &[u8], options: &CsvOptions, sanitize: F) -> String
+where
+    F: Fn(&[u8]) -> String,
+{
+    let mut srstxt = String::with_capacity(text.len());
+    let itxtlen = text.len();
+    let mut ic: usize = 0;
+    let mut icolumn: usize = 0;
+
+    while ic < itxtlen {
+        let (iend, bquoted) = find_field_end(text, ic, options);
+        let field = &text[ic..iend];
+
+        if options.is_column_selected(icolumn) {
+            let sfield = if bquoted {
+                sanitize_field(&unquote_field(field, options), options, &sanitize)
+            } else {
+                sanitize_field(field, options, &sanitize)
+            };
+
+            push_field(&sfield, bquoted, &mut srstxt, options);
+        } else {
+            push_source_field(field, &mut srstxt, options, &sanitize);
+        }
+
+        ic = iend;
+
+        //------------------------
+        //Field Separators
+
+        if ic < itxtlen {
+            if text[ic] == options.delimiter {
+                srstxt.push(options.delimiter as char);
+                icolumn += 1;
+                ic += 1;
+            } else {
+                //The Line End is kept as in the Source
+                if text[ic] == b'\r' {
+                    srstxt.push('\r');
+                    ic += 1;
+                }
+
+                srstxt.push('\n');
+                icolumn = 0;
+                ic += 1;
+            } //if text[ic] == options.delimiter
+        } //if ic < itxtlen
+    } //while ic < itxtlen
+
+    srstxt
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn csv_records() {
+    let sanitize = |run: &[u8]| {
+        String::from_utf8_lossy(run)
+            .replace('\u{ab}', "\"")
+            .replace('\u{e9}', "e")
+            .replace('\u{2022}', ",\n")
+    };
+
+    assert_eq!(
+        sanitize_csv(
+            b"id,name,note\r\n1,\"Ren\xc3\xa9, J.\",\xc2\xab\xc3\xa9\r\n2,\"\"\"\xc3\xa9\"\"\",\xe2\x80\xa2\n",
+            &CsvOptions::csv(),
+            sanitize
+        ),
+        "id,name,note\r\n1,\"Rene, J.\",\"\"\"e\"\r\n2,\"\"\"e\"\"\",  \n"
+    );
+    assert_eq!(
+        sanitize_csv(
+            b"\xc3\xa9\t\xc3\xa9\t\"a\tb\"\n",
+            &CsvOptions {
+                columns: vec![1, 2],
+                ..CsvOptions::tsv()
+            },
+            sanitize
+        ),
+        "\u{e9}\te\t\"a\tb\"\n"
+    );
+
+    //The invalid Bytes of the other Columns are sanitized as well
+    assert_eq!(
+        sanitize_csv(
+            b"Gr\xfcn \xc3\xa9,\xc3\xa9\n\"\xfc\xe2\x80\",x\n",
+            &CsvOptions {
+                columns: vec![1],
+                ..CsvOptions::csv()
+            },
+            |run: &[u8]| {
+                if run == b"\xfc" {
+                    String::from("ue")
+                } else if run.len() < 4 && str::from_utf8(run).is_err() {
+                    String::from("?,")
+                } else {
+                    String::from_utf8_lossy(run).replace('\u{e9}', "e")
+                }
+            }
+        ),
+        "Gruen \u{e9},e\n\"ue? \",x\n"
+    );
+    assert_eq!(
+        sanitize_csv(b"a,\"b\nc\",", &CsvOptions::csv(), sanitize),
+        "a,\"b\nc\","
+    );
+}
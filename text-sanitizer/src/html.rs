@@ -0,0 +1,394 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage html.rs
+
+* This module implements the HTML-aware sanitizing
+* It decodes the character references and keeps the markup intact
+*
+*---------------------------------
+* Requirements:
+* - no further Rust crates
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::char;
+use core::str;
+
+use crate::ascii::is_plain_ascii;
+
+//==============================================================================
+// Constants
+
+/// Names of the Latin-1 character references from U+00A0 to U+00FF
+const LATIN1_ENTITIES: [&str; 96] = [
+    "nbsp", "iexcl", "cent", "pound", "curren", "yen", "brvbar", "sect", "uml", "copy", "ordf",
+    "laquo", "not", "shy", "reg", "macr", "deg", "plusmn", "sup2", "sup3", "acute", "micro",
+    "para", "middot", "cedil", "sup1", "ordm", "raquo", "frac14", "frac12", "frac34", "iquest",
+    "Agrave", "Aacute", "Acirc", "Atilde", "Auml", "Aring", "AElig", "Ccedil", "Egrave", "Eacute",
+    "Ecirc", "Euml", "Igrave", "Iacute", "Icirc", "Iuml", "ETH", "Ntilde", "Ograve", "Oacute",
+    "Ocirc", "Otilde", "Ouml", "times", "Oslash", "Ugrave", "Uacute", "Ucirc", "Uuml", "Yacute",
+    "THORN", "szlig", "agrave", "aacute", "acirc", "atilde", "auml", "aring", "aelig", "ccedil",
+    "egrave", "eacute", "ecirc", "euml", "igrave", "iacute", "icirc", "iuml", "eth", "ntilde",
+    "ograve", "oacute", "ocirc", "otilde", "ouml", "divide", "oslash", "ugrave", "uacute", "ucirc",
+    "uuml", "yacute", "thorn", "yuml",
+];
+
+/// Further common character references beyond Latin-1
+const NAMED_ENTITIES: [(&str, char); 50] = [
+    ("quot", '"'),
+    ("amp", '&'),
+    ("apos", '\''),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("OElig", '\u{152}'),
+    ("oelig", '\u{153}'),
+    ("Scaron", '\u{160}'),
+    ("scaron", '\u{161}'),
+    ("Yuml", '\u{178}'),
+    ("fnof", '\u{192}'),
+    ("circ", '\u{2c6}'),
+    ("tilde", '\u{2dc}'),
+    ("ensp", '\u{2002}'),
+    ("emsp", '\u{2003}'),
+    ("thinsp", '\u{2009}'),
+    ("zwnj", '\u{200c}'),
+    ("zwj", '\u{200d}'),
+    ("lrm", '\u{200e}'),
+    ("rlm", '\u{200f}'),
+    ("ndash", '\u{2013}'),
+    ("mdash", '\u{2014}'),
+    ("lsquo", '\u{2018}'),
+    ("rsquo", '\u{2019}'),
+    ("sbquo", '\u{201a}'),
+    ("ldquo", '\u{201c}'),
+    ("rdquo", '\u{201d}'),
+    ("bdquo", '\u{201e}'),
+    ("dagger", '\u{2020}'),
+    ("Dagger", '\u{2021}'),
+    ("bull", '\u{2022}'),
+    ("hellip", '\u{2026}'),
+    ("permil", '\u{2030}'),
+    ("prime", '\u{2032}'),
+    ("Prime", '\u{2033}'),
+    ("lsaquo", '\u{2039}'),
+    ("rsaquo", '\u{203a}'),
+    ("euro", '\u{20ac}'),
+    ("trade", '\u{2122}'),
+    ("larr", '\u{2190}'),
+    ("uarr", '\u{2191}'),
+    ("rarr", '\u{2192}'),
+    ("darr", '\u{2193}'),
+    ("harr", '\u{2194}'),
+    ("minus", '\u{2212}'),
+    ("infin", '\u{221e}'),
+    ("ne", '\u{2260}'),
+    ("le", '\u{2264}'),
+    ("ge", '\u{2265}'),
+    ("hearts", '\u{2665}'),
+];
+
+/// Longest name of a character reference which is looked up
+const ENTITY_NAME_MAX: usize = 8;
+
+/// Elements whose content is raw text without character references
+const RAW_TEXT_ELEMENTS: [&[u8]; 2] = [b"script", b"style"];
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Looks up the character of a named character reference like "auml".
+fn lookup_entity(name: &str) -> Option<char> {
+    if let Some(ipos) = LATIN1_ENTITIES.iter().position(|sname| *sname == name) {
+        return char::from_u32(0xa0 + ipos as u32);
+    }
+
+    NAMED_ENTITIES
+        .iter()
+        .find(|(sname, _)| *sname == name)
+        .map(|(_, c)| *c)
+}
+
+/// Parses the character reference starting with the '&' at `start`.\
+/// Named references like "&auml;", decimal references like "&#252;" and hexadecimal
+/// references like "&#xfc;" are recognized. They must be terminated by a ';'.\
+/// Returns the referenced character and the position after the reference.
+fn parse_reference(text: &[u8], start: usize) -> Option<(char, usize)> {
+    let iend = text[start..]
+        .iter()
+        .take(ENTITY_NAME_MAX + 3)
+        .position(|uc| *uc == b';')
+        .map(|ipos| start + ipos)?;
+    let sname = str::from_utf8(&text[(start + 1)..iend]).ok()?;
+
+    let vname = sname.as_bytes();
+
+    let oc = if vname.first() == Some(&b'#') {
+        let ocode = if vname.get(1) == Some(&b'x') || vname.get(1) == Some(&b'X') {
+            u32::from_str_radix(&sname[2..], 16).ok()
+        } else {
+            sname[1..].parse::<u32>().ok()
+        };
+
+        //The NUL Character is never referenced
+        ocode.filter(|icode| *icode != 0).and_then(char::from_u32)
+    } else {
+        lookup_entity(sname)
+    };
+
+    oc.map(|c| (c, iend + 1))
+}
+
+/// Checks whether a referenced character must stay escaped.\
+/// These are the characters of the markup syntax and the non-breaking space
+/// which keeps the layout of the page.
+fn is_kept_escaped(c: char) -> bool {
+    match c {
+        '<' | '>' | '&' | '"' | '\'' | '\u{a0}' => true,
+        _ => false,
+    }
+}
+
+/// Checks whether a '<' at `start` opens markup like a tag, a comment
+/// or a declaration. Otherwise it is a literal character of the text.
+fn is_markup_start(text: &[u8], start: usize) -> bool {
+    match text.get(start + 1) {
+        Some(uc) => uc.is_ascii_alphabetic() || *uc == b'/' || *uc == b'!' || *uc == b'?',
+        None => false,
+    }
+}
+
+/// Finds the position of `pattern` at or after `start` ignoring the ASCII case.
+fn find_sequence(text: &[u8], start: usize, pattern: &[u8]) -> Option<usize> {
+    if text.len() < pattern.len() {
+        return None;
+    }
+
+    (start..=(text.len() - pattern.len()))
+        .find(|ipos| text[*ipos..(*ipos + pattern.len())].eq_ignore_ascii_case(pattern))
+}
+
+/// Finds the position after the '>' closing the tag starting at `start`.\
+/// A '>' within a quoted attribute value does not close the tag.
+fn find_tag_end(text: &[u8], start: usize) -> usize {
+    let mut oquote: Option<u8> = None;
+    let mut bvalue = false;
+
+    for (ipos, uc) in text.iter().enumerate().skip(start + 1) {
+        match oquote {
+            Some(quote) => {
+                if *uc == quote {
+                    oquote = None;
+                }
+            }
+            None => match *uc {
+                b'>' => return ipos + 1,
+                b'"' | b'\'' if bvalue => oquote = Some(*uc),
+                b'=' => bvalue = true,
+                b' ' | b'\t' | b'\n' | b'\r' => {}
+                _ => bvalue = false,
+            },
+        } //match oquote
+    } //for (ipos, uc) in text.iter().enumerate().skip(start + 1)
+
+    text.len()
+}
+
+/// Checks whether the tag opens an element with raw text content like "script".\
+/// Returns the name of the element.
+fn find_raw_text_element(tag: &[u8]) -> Option<&'static [u8]> {
+    RAW_TEXT_ELEMENTS
+        .iter()
+        .find(|sname| {
+            tag.len() > sname.len() + 1
+                && tag[1..(sname.len() + 1)].eq_ignore_ascii_case(sname)
+                && match tag[sname.len() + 1] {
+                    b'>' | b'/' | b' ' | b'\t' | b'\n' | b'\r' => true,
+                    _ => false,
+                }
+        })
+        .copied()
+}
+
+/// Adds the sanitized replacements with the markup characters escaped.\
+/// Quotes only need to be escaped within tags.
+fn push_escaped(replacement: &str, srstxt: &mut String, bquotes: bool) {
+    for c in replacement.chars() {
+        match c {
+            '<' => srstxt.push_str("&lt;"),
+            '>' => srstxt.push_str("&gt;"),
+            '&' => srstxt.push_str("&amp;"),
+            '"' if bquotes => srstxt.push_str("&quot;"),
+            '\'' if bquotes => srstxt.push_str("&#39;"),
+            _ => srstxt.push(c),
+        }
+    }
+}
+
+/// Sanitizes the collected bytes which need attention.
+fn flush_special<F>(vspecial: &mut Vec<u8>, srstxt: &mut String, sanitize: &F, bquotes: bool)
+where
+    F: Fn(&[u8]) -> String,
+{
+    if !vspecial.is_empty() {
+        push_escaped(&sanitize(vspecial), srstxt, bquotes);
+
+        vspecial.clear();
+    }
+}
+
+/// Sanitizes text or a tag with the character references decoded.\
+/// Plain ASCII characters are copied unchanged. So the markup syntax stays intact.
+fn push_text<F>(text: &[u8], srstxt: &mut String, sanitize: &F, bquotes: bool)
+where
+    F: Fn(&[u8]) -> String,
+{
+    let mut vspecial: Vec<u8> = Vec::new();
+    let mut ic: usize = 0;
+
+    while ic < text.len() {
+        let uc = text[ic];
+
+        if uc == b'&' {
+            if let Some((c, iend)) = parse_reference(text, ic) {
+                if is_kept_escaped(c) {
+                    flush_special(&mut vspecial, srstxt, sanitize, bquotes);
+                    srstxt.push_str(&String::from_utf8_lossy(&text[ic..iend]));
+                } else if c.is_ascii() && is_plain_ascii(c as u8) {
+                    flush_special(&mut vspecial, srstxt, sanitize, bquotes);
+                    srstxt.push(c);
+                } else {
+                    let mut vbuffer = [0u8; 4];
+
+                    vspecial.extend_from_slice(c.encode_utf8(&mut vbuffer).as_bytes());
+                }
+
+                ic = iend;
+
+                continue;
+            } //if let Some((c, iend)) = parse_reference(text, ic)
+        } //if uc == b'&'
+
+        if is_plain_ascii(uc) {
+            flush_special(&mut vspecial, srstxt, sanitize, bquotes);
+            srstxt.push(uc as char);
+        } else {
+            vspecial.push(uc);
+        }
+
+        ic += 1;
+    } //while ic < text.len()
+
+    flush_special(&mut vspecial, srstxt, sanitize, bquotes);
+}
+
+/// Sanitizes raw text data as HTML.\
+/// Character references in the text and in the attribute values are decoded before
+/// `sanitize` replaces them. Replacements with markup characters are escaped.
+/// References to markup characters like "&lt;" stay escaped.
+/// Comments and the content of "script" and "style" elements are sanitized
+/// without decoding.
+pub fn sanitize_html<F>(text: &[u8], sanitize: F) -> String
+where
+    F: Fn(&[u8]) -> String,
+{
+    let mut srstxt = String::with_capacity(text.len());
+    let itxtlen = text.len();
+    let mut ic: usize = 0;
+
+    while ic < itxtlen {
+        if text[ic] == b'<' && is_markup_start(text, ic) {
+            if text[ic..].starts_with(b"<!--") {
+                //------------------------
+                //Comments
+
+                let iend = find_sequence(text, ic + 4, b"-->").map_or(itxtlen, |ipos| ipos + 3);
+
+                srstxt.push_str(&sanitize(&text[ic..iend]));
+                ic = iend;
+            } else {
+                //------------------------
+                //Tags
+
+                let iend = find_tag_end(text, ic);
+                let oraw = find_raw_text_element(&text[ic..iend]);
+
+                push_text(&text[ic..iend], &mut srstxt, &sanitize, true);
+                ic = iend;
+
+                if let Some(sname) = oraw {
+                    let mut vclose = Vec::with_capacity(sname.len() + 2);
+
+                    vclose.extend_from_slice(b"</");
+                    vclose.extend_from_slice(sname);
+
+                    let iend = find_sequence(text, ic, &vclose).unwrap_or(itxtlen);
+
+                    srstxt.push_str(&sanitize(&text[ic..iend]));
+                    ic = iend;
+                } //if let Some(sname) = oraw
+            } //if text[ic..].starts_with(b"<!--")
+        } else {
+            //------------------------
+            //Text Content
+
+            let mut iend = ic + 1;
+
+            while iend < itxtlen && !(text[iend] == b'<' && is_markup_start(text, iend)) {
+                iend += 1;
+            }
+
+            push_text(&text[ic..iend], &mut srstxt, &sanitize, false);
+            ic = iend;
+        } //if text[ic] == b'<' && is_markup_start(text, ic)
+    } //while ic < itxtlen
+
+    srstxt
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn html_references() {
+    assert_eq!(parse_reference(b"&auml;", 0), Some(('\u{e4}', 6)));
+    assert_eq!(parse_reference(b"a &#252; b", 2), Some(('\u{fc}', 8)));
+    assert_eq!(parse_reference(b"&#xFC;", 0), Some(('\u{fc}', 6)));
+    assert_eq!(parse_reference(b"&euro;", 0), Some(('\u{20ac}', 6)));
+    assert_eq!(parse_reference(b"&#0;", 0), None);
+    assert_eq!(parse_reference(b"&#xd800;", 0), None);
+    assert_eq!(parse_reference(b"&unknown;", 0), None);
+    assert_eq!(parse_reference(b"&auml", 0), None);
+    assert_eq!(parse_reference(b"R&D; ", 1), None);
+}
+
+#[test]
+fn html_markup() {
+    let sanitize = |run: &[u8]| String::from_utf8_lossy(run).replace('\u{2764}', "<3");
+
+    assert_eq!(
+        sanitize_html(b"<p title=\"a > b\">&#x2764; &lt;3</p>", sanitize),
+        "<p title=\"a > b\">&lt;3 &lt;3</p>"
+    );
+    assert_eq!(
+        sanitize_html(b"<a href=\"?a=1&amp;b=&quot;2&quot;\">1 < 2</a>", sanitize),
+        "<a href=\"?a=1&amp;b=&quot;2&quot;\">1 < 2</a>"
+    );
+    assert_eq!(
+        sanitize_html(b"<SCRIPT>s = '&hearts;\xe2\x9d\xa4';</script>", sanitize),
+        "<SCRIPT>s = '&hearts;<3';</script>"
+    );
+    assert_eq!(
+        sanitize_html(b"<!-- &hearts; -->&hearts;", sanitize),
+        "<!-- &hearts; -->\u{2665}"
+    );
+    assert_eq!(
+        sanitize_html(
+            b"<img alt='\xe2\x9d\xa4'>\"\xe2\x9d\xa4\"",
+            |run: &[u8]| { String::from_utf8_lossy(run).replace('\u{2764}', "'<3'") }
+        ),
+        "<img alt='&#39;&lt;3&#39;'>\"'&lt;3'\""
+    );
+}
@@ -0,0 +1,505 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage json.rs
+
+* This module implements the JSON-aware sanitizing
+* It parses the JSON document and sanitizes only the string values
+* The whitespace, numbers and literals of the document are kept unchanged
+*
+*---------------------------------
+* Requirements:
+* - no further Rust crates
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::char;
+use core::fmt;
+
+//==============================================================================
+// Constants
+
+/// Deepest nesting of objects and arrays which is parsed
+const JSON_DEPTH_MAX: usize = 512;
+
+//==============================================================================
+// Structure JsonError Declaration
+
+/// Error of a document which is not valid JSON.\
+/// The position is the byte offset within the document where the parsing failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonError {
+    /// byte offset of the error within the document.
+    pub position: usize,
+    /// description of the error.
+    pub message: &'static str,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonError {}
+
+//==============================================================================
+// Structure JsonSanitizer Declaration
+
+/// Parser which copies the JSON document and sanitizes its strings on the way.
+struct JsonSanitizer<'a, F> {
+    _text: &'a [u8],
+    _ipos: usize,
+    _srstxt: String,
+    _bkeys: bool,
+    _sanitize: F,
+}
+
+//==============================================================================
+// Structure JsonSanitizer Implementation
+
+impl<'a, F> JsonSanitizer<'a, F>
+where
+    F: Fn(&[u8]) -> String,
+{
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    fn new(text: &'a [u8], bkeys: bool, sanitize: F) -> JsonSanitizer<'a, F> {
+        JsonSanitizer {
+            _text: text,
+            _ipos: 0,
+            _srstxt: String::with_capacity(text.len()),
+            _bkeys: bkeys,
+            _sanitize: sanitize,
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Parsing Methods
+     */
+
+    fn parse_document(mut self) -> Result<String, JsonError> {
+        self.copy_whitespace();
+        self.parse_value(0)?;
+        self.copy_whitespace();
+
+        if self._ipos < self._text.len() {
+            return Err(self.error("unexpected data after the document"));
+        }
+
+        Ok(self._srstxt)
+    }
+
+    fn parse_value(&mut self, idepth: usize) -> Result<(), JsonError> {
+        match self.peek() {
+            Some(b'{') => self.parse_object(idepth + 1),
+            Some(b'[') => self.parse_array(idepth + 1),
+            Some(b'"') => {
+                let vstring = self.parse_string()?;
+
+                self.push_sanitized(&vstring);
+
+                Ok(())
+            }
+            Some(b't') => self.copy_literal(b"true"),
+            Some(b'f') => self.copy_literal(b"false"),
+            Some(b'n') => self.copy_literal(b"null"),
+            Some(b'-') | Some(b'0'..=b'9') => self.copy_number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of document")),
+        }
+    }
+
+    fn parse_object(&mut self, idepth: usize) -> Result<(), JsonError> {
+        if idepth > JSON_DEPTH_MAX {
+            return Err(self.error("document nested too deeply"));
+        }
+
+        self.copy_byte();
+        self.copy_whitespace();
+
+        if self.peek() == Some(b'}') {
+            self.copy_byte();
+
+            return Ok(());
+        }
+
+        loop {
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected a string as key"));
+            }
+
+            let istart = self._ipos;
+            let vkey = self.parse_string()?;
+
+            if self._bkeys {
+                self.push_sanitized(&vkey);
+            } else {
+                //The Key is kept as in the Source
+                self._srstxt
+                    .push_str(&String::from_utf8_lossy(&self._text[istart..self._ipos]));
+            }
+
+            self.copy_whitespace();
+
+            if self.peek() != Some(b':') {
+                return Err(self.error("expected ':' after the key"));
+            }
+
+            self.copy_byte();
+            self.copy_whitespace();
+            self.parse_value(idepth)?;
+            self.copy_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.copy_byte();
+                    self.copy_whitespace();
+                }
+                Some(b'}') => {
+                    self.copy_byte();
+
+                    return Ok(());
+                }
+                _ => return Err(self.error("expected ',' or '}' in object")),
+            } //match self.peek()
+        } //loop
+    }
+
+    fn parse_array(&mut self, idepth: usize) -> Result<(), JsonError> {
+        if idepth > JSON_DEPTH_MAX {
+            return Err(self.error("document nested too deeply"));
+        }
+
+        self.copy_byte();
+        self.copy_whitespace();
+
+        if self.peek() == Some(b']') {
+            self.copy_byte();
+
+            return Ok(());
+        }
+
+        loop {
+            self.parse_value(idepth)?;
+            self.copy_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.copy_byte();
+                    self.copy_whitespace();
+                }
+                Some(b']') => {
+                    self.copy_byte();
+
+                    return Ok(());
+                }
+                _ => return Err(self.error("expected ',' or ']' in array")),
+            } //match self.peek()
+        } //loop
+    }
+
+    /// Parses a string and returns its raw content with the escape sequences decoded.\
+    /// Raw bytes which are not valid UTF-8 are kept for the sanitizer.
+    fn parse_string(&mut self) -> Result<Vec<u8>, JsonError> {
+        let mut vstring: Vec<u8> = Vec::new();
+
+        //Skip the opening Quote
+        self._ipos += 1;
+
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self._ipos += 1;
+
+                    return Ok(vstring);
+                }
+                Some(b'\\') => {
+                    self._ipos += 1;
+
+                    let uc = match self.peek() {
+                        Some(b'"') => b'"',
+                        Some(b'\\') => b'\\',
+                        Some(b'/') => b'/',
+                        Some(b'b') => 8,
+                        Some(b'f') => 12,
+                        Some(b'n') => b'\n',
+                        Some(b'r') => b'\r',
+                        Some(b't') => b'\t',
+                        Some(b'u') => {
+                            let c = self.parse_unicode_escape()?;
+                            let mut vbuffer = [0u8; 4];
+
+                            vstring.extend_from_slice(c.encode_utf8(&mut vbuffer).as_bytes());
+
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+
+                    vstring.push(uc);
+                    self._ipos += 1;
+                }
+                Some(uc) if uc < 32 => {
+                    return Err(self.error("unescaped control character in string"))
+                }
+                Some(uc) => {
+                    vstring.push(uc);
+                    self._ipos += 1;
+                }
+                None => return Err(self.error("unterminated string")),
+            } //match self.peek()
+        } //loop
+    }
+
+    /// Parses an escape sequence like "\u00ed" starting at the 'u'.\
+    /// Surrogate pairs are combined. Lone surrogates become U+FFFD.
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonError> {
+        let icode = self.parse_hex4()?;
+
+        if (0xd800..0xdc00).contains(&icode) && self._text[self._ipos..].starts_with(b"\\u") {
+            let ipos = self._ipos;
+
+            self._ipos += 1;
+
+            let ilow = self.parse_hex4()?;
+
+            if (0xdc00..0xe000).contains(&ilow) {
+                let ipair = 0x10000 + ((icode - 0xd800) << 10) + (ilow - 0xdc00);
+
+                return Ok(char::from_u32(ipair).unwrap_or('\u{fffd}'));
+            }
+
+            //The second Escape Sequence is parsed on its own
+            self._ipos = ipos;
+        } //if (0xd800..0xdc00).contains(&icode) && ...
+
+        Ok(char::from_u32(icode).unwrap_or('\u{fffd}'))
+    }
+
+    /// Parses the 4 hexadecimal digits after the 'u' of an escape sequence.
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let istart = self._ipos + 1;
+        let iend = istart + 4;
+
+        if iend > self._text.len()
+            || !self._text[istart..iend]
+                .iter()
+                .all(|uc| uc.is_ascii_hexdigit())
+        {
+            return Err(self.error("invalid unicode escape sequence"));
+        }
+
+        let mut icode: u32 = 0;
+
+        for uc in &self._text[istart..iend] {
+            icode = icode * 16 + (*uc as char).to_digit(16).unwrap_or(0);
+        }
+
+        self._ipos = iend;
+
+        Ok(icode)
+    }
+
+    fn copy_literal(&mut self, literal: &[u8]) -> Result<(), JsonError> {
+        if !self._text[self._ipos..].starts_with(literal) {
+            return Err(self.error("invalid literal"));
+        }
+
+        self.copy_range(self._ipos + literal.len());
+
+        Ok(())
+    }
+
+    fn copy_number(&mut self) -> Result<(), JsonError> {
+        let mut ipos = self._ipos;
+
+        if self._text[ipos] == b'-' {
+            ipos += 1;
+        }
+
+        match self._text.get(ipos) {
+            Some(b'0') => ipos += 1,
+            Some(b'1'..=b'9') => ipos = self.skip_digits(ipos),
+            _ => return Err(self.error("invalid number")),
+        }
+
+        if self._text.get(ipos) == Some(&b'.') {
+            if !self.is_digit_at(ipos + 1) {
+                return Err(self.error("invalid number"));
+            }
+
+            ipos = self.skip_digits(ipos + 1);
+        }
+
+        if self._text.get(ipos) == Some(&b'e') || self._text.get(ipos) == Some(&b'E') {
+            ipos += 1;
+
+            if self._text.get(ipos) == Some(&b'+') || self._text.get(ipos) == Some(&b'-') {
+                ipos += 1;
+            }
+
+            if !self.is_digit_at(ipos) {
+                return Err(self.error("invalid number"));
+            }
+
+            ipos = self.skip_digits(ipos);
+        } //if self._text.get(ipos) == Some(&b'e') || ...
+
+        self.copy_range(ipos);
+
+        Ok(())
+    }
+
+    /*----------------------------------------------------------------------------
+     * Auxiliary Methods
+     */
+
+    fn peek(&self) -> Option<u8> {
+        self._text.get(self._ipos).copied()
+    }
+
+    fn is_digit_at(&self, ipos: usize) -> bool {
+        match self._text.get(ipos) {
+            Some(uc) => uc.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    fn skip_digits(&self, mut ipos: usize) -> usize {
+        while self.is_digit_at(ipos) {
+            ipos += 1;
+        }
+
+        ipos
+    }
+
+    fn copy_byte(&mut self) {
+        self.copy_range(self._ipos + 1);
+    }
+
+    fn copy_range(&mut self, iend: usize) {
+        //The structural Characters are all ASCII
+        for uc in &self._text[self._ipos..iend] {
+            self._srstxt.push(*uc as char);
+        }
+
+        self._ipos = iend;
+    }
+
+    fn copy_whitespace(&mut self) {
+        let mut iend = self._ipos;
+
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self._text.get(iend) {
+            iend += 1;
+        }
+
+        self.copy_range(iend);
+    }
+
+    /// Sanitizes the decoded string and writes it back as valid JSON string.
+    fn push_sanitized(&mut self, text: &[u8]) {
+        let ssanitized = (self._sanitize)(text);
+
+        self._srstxt.push('"');
+
+        for c in ssanitized.chars() {
+            match c {
+                '"' => self._srstxt.push_str("\\\""),
+                '\\' => self._srstxt.push_str("\\\\"),
+                '\n' => self._srstxt.push_str("\\n"),
+                '\r' => self._srstxt.push_str("\\r"),
+                '\t' => self._srstxt.push_str("\\t"),
+                '\u{8}' => self._srstxt.push_str("\\b"),
+                '\u{c}' => self._srstxt.push_str("\\f"),
+                c if (c as u32) < 32 => {
+                    self._srstxt
+                        .push_str(&alloc::format!("\\u{:04x}", c as u32));
+                }
+                _ => self._srstxt.push(c),
+            }
+        } //for c in ssanitized.chars()
+
+        self._srstxt.push('"');
+    }
+
+    fn error(&self, message: &'static str) -> JsonError {
+        JsonError {
+            position: self._ipos,
+            message,
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Sanitizes a JSON document.\
+/// The escape sequences like "\u00ed" of each string value are decoded before `sanitize`
+/// replaces them. The sanitized strings are escaped again. So the result is valid JSON.
+/// The keys of the objects are only sanitized with `bkeys`.
+/// The whitespace, numbers and literals are kept unchanged.
+pub fn sanitize_json<F>(text: &[u8], bkeys: bool, sanitize: F) -> Result<String, JsonError>
+where
+    F: Fn(&[u8]) -> String,
+{
+    JsonSanitizer::new(text, bkeys, sanitize).parse_document()
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn json_strings() {
+    let sanitize = |text: &[u8]| String::from_utf8_lossy(text).replace('\u{ed}', "i");
+
+    assert_eq!(
+        sanitize_json(
+            b"{\"L\\u00ednea\": [\"D\\u00eda\", 5, -1.5e3, true, null]}",
+            false,
+            sanitize
+        ),
+        Ok(String::from(
+            "{\"L\\u00ednea\": [\"Dia\", 5, -1.5e3, true, null]}"
+        ))
+    );
+    assert_eq!(
+        sanitize_json(b"{ \"L\xc3\xadnea\" :\n\"a\\\"\\/\\tb\" }", true, sanitize),
+        Ok(String::from("{ \"Linea\" :\n\"a\\\"/\\tb\" }"))
+    );
+    assert_eq!(
+        sanitize_json(b"\"\\ud83d\\udc96 \\ud83d\"", false, |text: &[u8]| {
+            String::from_utf8_lossy(text).into_owned()
+        }),
+        Ok(String::from("\"\u{1f496} \u{fffd}\""))
+    );
+}
+
+#[test]
+fn json_errors() {
+    let sanitize = |text: &[u8]| String::from_utf8_lossy(text).into_owned();
+
+    assert_eq!(
+        sanitize_json(b"{\"a\": 01}", false, sanitize),
+        Err(JsonError {
+            position: 7,
+            message: "expected ',' or '}' in object"
+        })
+    );
+    assert_eq!(
+        sanitize_json(b"[\"a\\x\"]", false, sanitize).map_err(|e| e.position),
+        Err(4)
+    );
+    assert_eq!(
+        sanitize_json(b"[1, 2", false, sanitize).map_err(|e| e.message),
+        Err("expected ',' or ']' in array")
+    );
+    assert!(sanitize_json(b"{} []", false, sanitize).is_err());
+    assert!(sanitize_json(b"\"a\nb\"", false, sanitize).is_err());
+    assert!(sanitize_json(&[b'['; 1000], false, sanitize).is_err());
+}
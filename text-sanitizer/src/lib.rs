@@ -23,7 +23,37 @@
 //! The conversion map helps also to rescue unrecognized bytes with custom mappings.
 //! So, a wrongly encoded byte like "(?80)" can be mapped to "EUR" which correctly
 //! encoded should be "U+20AC"
+//!
+//! The cargo feature `std` is enabled by default. Without it the library only needs
+//! the `alloc` crate. Then the maps are `BTreeMap`s and the debug messages go
+//! to a `DebugSink`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The crate still builds with rustc 1.41 which cannot derive the Default for enums
+// and has no matches! macro
+#![allow(clippy::derivable_impls, clippy::match_like_matches_macro)]
+
+extern crate alloc;
 
+mod ascii;
+mod csv;
+mod html;
+mod json;
+#[cfg(feature = "std")]
+mod lines;
+pub mod locale;
 pub mod sanitizer;
+pub mod sink;
+mod width;
 
-pub use sanitizer::{ConversionMap, LanguageMap, TextSanitizer};
+pub use csv::CsvOptions;
+pub use json::JsonError;
+#[cfg(feature = "std")]
+pub use lines::SanitizedLines;
+pub use sanitizer::{
+    CompiledSanitizer, ConversionMap, Fallback, LanguageMap, SanitizeOptions, TargetCharset,
+    TextSanitizer, TextSanitizerBuilder,
+};
+pub use sink::DebugSink;
+#[cfg(feature = "std")]
+pub use sink::StdoutSink;
@@ -0,0 +1,109 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage lines.rs
+
+* This module implements the line-oriented sanitizing of readers
+* Each line is sanitized on its own and is reported with its line number
+*
+*---------------------------------
+* Requirements:
+* - The cargo feature "std" must be enabled
+*/
+
+use std::io::{self, BufRead};
+
+use crate::sanitizer::TextSanitizer;
+
+//==============================================================================
+// Structure SanitizedLines Declaration
+
+/// Iterator over the sanitized lines of a `BufRead` created with
+/// `TextSanitizer::sanitize_lines()`.\
+/// Each item holds the line number starting at 1, the sanitized line without
+/// its line ending and whether any character of the line was replaced.
+/// Read errors of the reader are passed on.
+#[derive(Debug)]
+pub struct SanitizedLines<'a, R> {
+    _sanitizer: &'a TextSanitizer,
+    _reader: R,
+    _iline: usize,
+    _vbuffer: Vec<u8>,
+}
+
+//==============================================================================
+// Structure SanitizedLines Implementation
+
+impl<'a, R: BufRead> SanitizedLines<'a, R> {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub(crate) fn new(sanitizer: &'a TextSanitizer, reader: R) -> SanitizedLines<'a, R> {
+        SanitizedLines {
+            _sanitizer: sanitizer,
+            _reader: reader,
+            _iline: 0,
+            _vbuffer: Vec::new(),
+        }
+    }
+}
+
+impl<'a, R: BufRead> Iterator for SanitizedLines<'a, R> {
+    type Item = io::Result<(usize, String, bool)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self._vbuffer.clear();
+
+        match self._reader.read_until(b'\n', &mut self._vbuffer) {
+            Ok(0) => None,
+            Ok(_) => {
+                let mut iend = self._vbuffer.len();
+
+                //The Line Ending is not part of the Line
+                if self._vbuffer[iend - 1] == b'\n' {
+                    iend -= 1;
+
+                    if iend > 0 && self._vbuffer[iend - 1] == b'\r' {
+                        iend -= 1;
+                    }
+                }
+
+                let line = &self._vbuffer[..iend];
+                let ssanitized = self._sanitizer.sanitize_u8(line);
+                let breplaced = ssanitized.as_bytes() != line;
+
+                self._iline += 1;
+
+                Some(Ok((self._iline, ssanitized, breplaced)))
+            }
+            Err(e) => Some(Err(e)),
+        } //match self._reader.read_until(b'\n', &mut self._vbuffer)
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn sanitized_lines() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("de");
+
+    let vtext = b"Gr\xc3\xbcn\r\nRot \xc3\nBlau\n\xfc";
+    let vlines: Vec<(usize, String, bool)> = SanitizedLines::new(&sanitizer, &vtext[..])
+        .map(|line| line.expect("line not readable"))
+        .collect();
+
+    assert_eq!(
+        vlines,
+        vec![
+            (1, String::from("Gruen"), true),
+            (2, String::from("Rot (?c3)"), true),
+            (3, String::from("Blau"), false),
+            (4, String::from("ue"), true),
+        ]
+    );
+}
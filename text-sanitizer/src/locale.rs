@@ -0,0 +1,160 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage locale.rs
+
+* This module finds the languages of the system locale
+* The locale names like "es_ES.UTF-8" are reduced to their language shortcode
+*
+*---------------------------------
+* Requirements:
+* - The Standard Library is only needed to read the environment variables
+*/
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+//==============================================================================
+// Constants
+
+/// Environment variables of the locale by their priority
+pub const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Environment variable of the GNU list of languages like "de:fr"
+pub const LANGUAGE_VARIABLE: &str = "LANGUAGE";
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Returns the language shortcode of a locale name like "es_ES.UTF-8" or "de_DE@euro".\
+/// The locales "C" and "POSIX" and malformed names have no language.
+pub fn parse_locale_language(locale: &str) -> Option<String> {
+    let slanguage = locale.split(&['_', '.', '@'][..]).next()?;
+
+    if (slanguage.len() == 2 || slanguage.len() == 3)
+        && slanguage.bytes().all(|uc| uc.is_ascii_alphabetic())
+    {
+        Some(slanguage.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+/// Returns the language shortcodes of the `locales` in their order without duplicates.
+///
+/// # Example:
+///
+/// ```
+///    use text_sanitizer::locale::build_locale_languages;
+///
+///    let vlangs = build_locale_languages(&["C", "es_ES.UTF-8", "de_DE@euro"]);
+///
+///    assert_eq!(vlangs, vec!["es", "de"]);
+/// ```
+pub fn build_locale_languages(locales: &[&str]) -> Vec<String> {
+    let mut vlangs: Vec<String> = Vec::with_capacity(locales.len());
+
+    for slanguage in locales
+        .iter()
+        .filter_map(|slocale| parse_locale_language(slocale))
+    {
+        if !vlangs.contains(&slanguage) {
+            vlangs.push(slanguage);
+        }
+    }
+
+    vlangs
+}
+
+/// Returns the language shortcodes of the locale like GNU gettext selects them.\
+/// The first of the `locales` of "LC_ALL", "LC_MESSAGES" and "LANG" which is set and
+/// not empty is the locale. The GNU list `olanguages` of "LANGUAGE" like "de:fr" takes
+/// precedence over it. Both are ignored when the locale is "C" or "POSIX".
+///
+/// # Example:
+///
+/// ```
+///    use text_sanitizer::locale::select_locale_languages;
+///
+///    let vlangs = select_locale_languages(&[Some(""), None, Some("es_ES.UTF-8")], Some("de:fr"));
+///
+///    assert_eq!(vlangs, vec!["de", "fr", "es"]);
+/// ```
+pub fn select_locale_languages(locales: &[Option<&str>], olanguages: Option<&str>) -> Vec<String> {
+    let mut vlocales: Vec<&str> = Vec::new();
+    let olocale = locales
+        .iter()
+        .filter_map(|olocale| *olocale)
+        .find(|slocale| !slocale.is_empty());
+
+    if let Some(slocale) = olocale {
+        if parse_locale_language(slocale).is_some() {
+            if let Some(slist) = olanguages {
+                vlocales.extend(slist.split(':'));
+            }
+
+            vlocales.push(slocale);
+        }
+    } //if let Some(slocale) = olocale
+
+    build_locale_languages(&vlocales)
+}
+
+/// Returns the language shortcodes of the first environment variable of "LC_ALL",
+/// "LC_MESSAGES" and "LANG" which is set preceded by the list of "LANGUAGE".\
+/// See `select_locale_languages()`
+#[cfg(feature = "std")]
+pub fn find_locale_languages() -> Vec<String> {
+    let vlocales: Vec<Option<String>> = LOCALE_VARIABLES
+        .iter()
+        .map(|svariable| std::env::var(svariable).ok())
+        .collect();
+    let vlocales: Vec<Option<&str>> = vlocales.iter().map(|olocale| olocale.as_deref()).collect();
+    let olanguages = std::env::var(LANGUAGE_VARIABLE).ok();
+
+    select_locale_languages(&vlocales, olanguages.as_deref())
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn locale_languages() {
+    assert_eq!(
+        parse_locale_language("es_ES.UTF-8"),
+        Some(String::from("es"))
+    );
+    assert_eq!(
+        parse_locale_language("de_AT@euro"),
+        Some(String::from("de"))
+    );
+    assert_eq!(parse_locale_language("FR"), Some(String::from("fr")));
+    assert_eq!(parse_locale_language("C.UTF-8"), None);
+    assert_eq!(parse_locale_language("POSIX"), None);
+    assert_eq!(parse_locale_language(""), None);
+    assert_eq!(
+        build_locale_languages(&["", "es_ES.UTF-8", "de_DE.UTF-8", "es_MX"]),
+        vec![String::from("es"), String::from("de")]
+    );
+
+    //Only the first Locale which is set counts
+    assert_eq!(
+        select_locale_languages(&[None, Some("de_DE.UTF-8"), Some("es_ES.UTF-8")], None),
+        vec![String::from("de")]
+    );
+    assert_eq!(
+        select_locale_languages(&[Some("C"), None, Some("es_ES.UTF-8")], Some("de")),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        select_locale_languages(&[Some("fr_FR"), None, None], Some("de_AT:fr::es")),
+        vec![String::from("de"), String::from("fr"), String::from("es")]
+    );
+
+    //The List of "LANGUAGE" takes Precedence over the Locale
+    assert_eq!(
+        select_locale_languages(&[None, None, Some("de_DE.UTF-8")], Some("es:de")),
+        vec![String::from("es"), String::from("de")]
+    );
+}
@@ -14,30 +14,285 @@
 
 extern crate serde;
 
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str;
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
-use std::str;
+
+use crate::ascii::{scan_plain_ascii, scan_special};
+use crate::csv::{sanitize_csv, CsvOptions};
+use crate::html::sanitize_html;
+use crate::json::{sanitize_json, JsonError};
+use crate::locale::build_locale_languages;
+use crate::sink::DebugSink;
+use crate::width::{char_width, str_width};
+
+#[cfg(feature = "std")]
+use crate::lines::SanitizedLines;
+#[cfg(feature = "std")]
+use crate::locale::find_locale_languages;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead};
+
+#[cfg(feature = "parallel")]
+use crate::ascii::find_safe_boundary;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+//==============================================================================
+// Constants
+
+/// Minimum size of the chunks that are sanitized in parallel
+#[cfg(feature = "parallel")]
+const PARALLEL_CHUNK_MIN: usize = 64 * 1024;
+
+/// Collection of the `LanguageMap`s by their language shortcode.\
+/// Without the cargo feature `std` it holds a `BTreeMap` instead of a `HashMap`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConversionMap(pub HashMap<String, LanguageMap>);
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Replacements by unicode codepoint or invalid byte in lowercase hexadecimal notation.\
+/// Without the cargo feature `std` it holds a `BTreeMap` instead of a `HashMap`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LanguageMap(pub HashMap<String, String>);
 
+//==============================================================================
+// Macros
+
+// Debug messages go to the DebugSink of the TextSanitizer
+macro_rules! debug_print {
+    ($sanitizer:expr, $($arg:tt)*) => {
+        $sanitizer.write_debug(format_args!($($arg)*))
+    };
+}
+
+macro_rules! debug_println {
+    ($sanitizer:expr) => {
+        $sanitizer.write_debug(format_args!("\n"))
+    };
+    ($sanitizer:expr, $($arg:tt)*) => {
+        $sanitizer.write_debug(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Language Shortcode of the merged `LanguageMap` of a `CompiledSanitizer`
+const COMPILED_LANGUAGE: &str = "*compiled*";
+
 //==============================================================================
 // Structure TextSanitizer Declaration
 
 /// Structure that holds reusable data as the "_ConversionMap_", the vector
 /// of Language Replacement Maps to be applied and runtime options like verbosity.
 
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 pub struct TextSanitizer {
     _oconv_map: Option<ConversionMap>,
     _vrqlangs: Vec<String>,
     _bquiet: bool,
     _bdebug: bool,
     _bprofiling: bool,
+    _fallback: Fallback,
+    _charset: TargetCharset,
+    _vkeeprngs: Vec<(char, char)>,
+    _bpreserve_width: bool,
+    _odebug_sink: Option<&'static dyn DebugSink>,
+}
+
+//==============================================================================
+// Enumeration Fallback Declaration
+
+/// Describes what happens to characters and bytes which are not found
+/// in any of the requested `LanguageMap`s.
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fallback {
+    /// Shows the unicode codepoint or the invalid byte like "(?fc)". This is the default.
+    Codepoint,
+    /// Removes the character or byte from the output.
+    Remove,
+    /// Replaces the character or byte with a fixed string like "?".
+    Replace(String),
+}
+
+impl Default for Fallback {
+    fn default() -> Self {
+        Fallback::Codepoint
+    }
+}
+
+//==============================================================================
+// Enumeration TargetCharset Declaration
+
+/// Describes the character set of the sanitized output.\
+/// Characters which are already valid in the target character set are passed through
+/// unchanged. Only the characters outside of it are looked up in the `LanguageMap`s.\
+/// The output is always a valid UTF-8 `String`. Control characters are never
+/// part of the target character set.
+///
+/// # Example:
+///
+/// Keep the German Umlauts but still replace the heart symbol
+/// ```
+///    use text_sanitizer::{TargetCharset, TextSanitizer};
+///
+///    let mut sanitizer = TextSanitizer::new();
+///
+///    sanitizer.set_target_charset(TargetCharset::Latin1);
+///    sanitizer.add_request_language(&"de");
+///    sanitizer.add_request_language(&"en");
+///
+///    assert_eq!(sanitizer.sanitize_string("Gr\u{fc}\u{df}e \u{2764}"), "Gr\u{fc}\u{df}e <3");
+/// ```
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TargetCharset {
+    /// Plain 7-bit ASCII. This is the default.
+    Ascii,
+    /// The printable characters of ISO-8859-1 up to "U+00FF".
+    Latin1,
+    /// All valid UTF-8 characters beside the exotic symbols.
+    /// These are the box drawings, the geometric shapes, the dingbats
+    /// ("U+2500" - "U+27BF"), the symbols and arrows ("U+2B00" - "U+2BFF"),
+    /// the emoji and pictographs ("U+1F000" - "U+1FAFF"), the variation selectors,
+    /// the private use areas and the replacement character "U+FFFD".
+    PrintableUtf8,
+}
+
+impl Default for TargetCharset {
+    fn default() -> Self {
+        TargetCharset::Ascii
+    }
+}
+
+impl TargetCharset {
+    /// Checks whether a character is passed through unchanged into this character set.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TargetCharset;
+    ///
+    ///    assert!(TargetCharset::Latin1.contains('\u{fc}'));
+    ///    assert!(!TargetCharset::Latin1.contains('\u{20ac}'));
+    ///    assert!(TargetCharset::PrintableUtf8.contains('\u{20ac}'));
+    ///    assert!(!TargetCharset::PrintableUtf8.contains('\u{2764}'));
+    /// ```
+    pub fn contains(&self, c: char) -> bool {
+        if c.is_control() {
+            return false;
+        }
+
+        let icode = c as u32;
+
+        match self {
+            TargetCharset::Ascii => icode < 0x80,
+            TargetCharset::Latin1 => icode <= 0xff,
+            TargetCharset::PrintableUtf8 => match icode {
+                0x2500..=0x27bf | 0x2b00..=0x2bff | 0x1f000..=0x1faff => false,
+                0xfe00..=0xfe0f | 0xe000..=0xf8ff | 0xf0000..=0x10ffff | 0xfffd => false,
+                _ => true,
+            },
+        }
+    }
+}
+
+//==============================================================================
+// Structure SanitizeOptions Declaration
+
+/// Typed runtime options of a `TextSanitizer`.\
+/// They replace the string representation like "-d -q" of the Procedural Interface.
+///
+/// # Example:
+///
+/// Create quiet `SanitizeOptions` which remove unmapped characters
+/// ```
+///    use text_sanitizer::{Fallback, SanitizeOptions};
+///
+///    let options = SanitizeOptions {
+///        quiet: true,
+///        fallback: Fallback::Remove,
+///        ..SanitizeOptions::default()
+///    };
+///
+///    assert!(!options.debug);
+/// ```
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SanitizeOptions {
+    /// do not print any messages not even errors.
+    pub quiet: bool,
+    /// do print detailed activity messages.
+    pub debug: bool,
+    /// profile the internal processing activities.
+    pub profiling: bool,
+    /// what happens to characters without any mapping.
+    pub fallback: Fallback,
+    /// character set of the sanitized output.
+    pub charset: TargetCharset,
+    /// keep the display width of each line like in the source.
+    pub preserve_width: bool,
+}
+
+impl SanitizeOptions {
+    /// Parses the runtime options from their string presentation.
+    ///
+    /// # Parameter:
+    ///
+    /// * `options` - string representation of the runtime options.\
+    ///   *  ` q | b ` - do not print warings.
+    ///   *  ` d | v ` - print detailed activity information.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::SanitizeOptions;
+    ///
+    ///    let options = SanitizeOptions::from_string(&"-d -q");
+    ///
+    ///    assert!(options.debug);
+    ///    assert!(options.quiet);
+    /// ```
+    pub fn from_string(options: &str) -> SanitizeOptions {
+        let mut parsed = SanitizeOptions::default();
+
+        parsed.merge_string(options);
+
+        parsed
+    }
+
+    #[doc(hidden)]
+    fn merge_string(&mut self, options: &str) {
+        let mut sopt;
+
+        if (!options.is_empty()) {
+            for prm in options.split_whitespace() {
+                if prm.starts_with("--") {
+                    sopt = prm.split_at(2).1;
+                    sopt.to_lowercase();
+                } else if prm.starts_with('-') {
+                    sopt = prm.split_at(1).1;
+                    sopt.to_lowercase();
+
+                    match sopt {
+                        "q" | "b" => self.quiet = true,
+                        "d" | "v" => self.debug = true,
+                        _ => {}
+                    } //match sopt.as_ref()
+                }
+            } //for mut prm in options.split_whitespace() {
+        } //if(!options.is_empty())
+    }
 }
 
 //==============================================================================
@@ -72,6 +327,11 @@ impl TextSanitizer {
             _bquiet: false,
             _bdebug: false,
             _bprofiling: false,
+            _fallback: Fallback::Codepoint,
+            _charset: TargetCharset::Ascii,
+            _vkeeprngs: Vec::new(),
+            _bpreserve_width: false,
+            _odebug_sink: None,
         };
 
         sanitizer.init();
@@ -95,8 +355,8 @@ impl TextSanitizer {
     ///    use text_sanitizer::{TextSanitizer, ConversionMap, LanguageMap};
     ///    use std::collections::HashMap;
     ///
-    ///    let mut conv_map = ConversionMap(HashMap::with_capacity(2));
-    ///    let mut lang_map = LanguageMap(HashMap::with_capacity(4));
+    ///    let mut conv_map = ConversionMap(HashMap::new());
+    ///    let mut lang_map = LanguageMap(HashMap::new());
     ///
     ///    lang_map.0.insert("d".to_string(), "".to_string());
     ///    lang_map.0.insert("1b".to_string(), "".to_string());
@@ -116,6 +376,11 @@ impl TextSanitizer {
             _bquiet: false,
             _bdebug: false,
             _bprofiling: false,
+            _fallback: Fallback::Codepoint,
+            _charset: TargetCharset::Ascii,
+            _vkeeprngs: Vec::new(),
+            _bpreserve_width: false,
+            _odebug_sink: None,
         };
 
         //Return the New TextSanitizer Object
@@ -148,6 +413,11 @@ impl TextSanitizer {
             _bquiet: bquiet,
             _bdebug: bdebug,
             _bprofiling: bprofiling,
+            _fallback: Fallback::Codepoint,
+            _charset: TargetCharset::Ascii,
+            _vkeeprngs: Vec::new(),
+            _bpreserve_width: false,
+            _odebug_sink: None,
         };
 
         sanitizer.init();
@@ -156,42 +426,49 @@ impl TextSanitizer {
         sanitizer
     }
 
+    /// This Constructor sets the runtime options from their string presentation.\
+    /// This is used for backward compatibility with the Procedural Interface.
+    ///
+    /// # Parameter:
+    ///
+    /// * `options` - string representation of the runtime options.
+    ///   See `SanitizeOptions::from_string()`
     pub fn new_with_options_string(options: &str) -> TextSanitizer {
-        //-------------------------------------
-        // Parse the Function Options
-
-        let mut sopt;
-
-        let mut bdbg: bool = false;
-        let mut bqt: bool = false;
-
-        if (!options.is_empty()) {
-            for prm in options.split_whitespace() {
-                if prm.starts_with("--") {
-                    sopt = prm.split_at(2).1;
-                    sopt.to_lowercase();
-                } else if prm.starts_with('-') {
-                    sopt = prm.split_at(1).1;
-                    sopt.to_lowercase();
-
-                    match sopt {
-                        "q" | "b" => bqt = true,
-                        "d" | "v" => bdbg = true,
-                        _ => {}
-                    } //match sopt.as_ref()
-                }
-            } //for mut prm in options.split_whitespace() {
-        } //if(!options.is_empty())
-
-        //-------------------------------------
-        // Create the TextSanitizer Object
+        TextSanitizer::new_with_sanitize_options(&SanitizeOptions::from_string(options))
+    }
 
+    /// This Constructor sets all runtime options from typed `SanitizeOptions`.
+    ///
+    /// # Example:
+    ///
+    /// Create a quiet `TextSanitizer` object which removes unmapped characters
+    /// ```
+    ///    use text_sanitizer::{Fallback, SanitizeOptions, TextSanitizer};
+    ///
+    ///    let options = SanitizeOptions {
+    ///        quiet: true,
+    ///        fallback: Fallback::Remove,
+    ///        ..SanitizeOptions::default()
+    ///    };
+    ///
+    ///    let mut sanitizer = TextSanitizer::new_with_sanitize_options(&options);
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///
+    ///    assert_eq!(sanitizer.sanitize_string("Ausfl\u{fc}ge"), "Ausflge");
+    /// ```
+    pub fn new_with_sanitize_options(options: &SanitizeOptions) -> TextSanitizer {
         let mut sanitizer = TextSanitizer {
             _oconv_map: None,
             _vrqlangs: Vec::new(),
-            _bquiet: bqt,
-            _bdebug: bdbg,
-            _bprofiling: false,
+            _bquiet: options.quiet,
+            _bdebug: options.debug,
+            _bprofiling: options.profiling,
+            _fallback: options.fallback.clone(),
+            _charset: options.charset,
+            _vkeeprngs: Vec::new(),
+            _bpreserve_width: options.preserve_width,
+            _odebug_sink: None,
         };
 
         sanitizer.init();
@@ -200,6 +477,12 @@ impl TextSanitizer {
         sanitizer
     }
 
+    /// Creates a `TextSanitizerBuilder` to configure a new `TextSanitizer`.\
+    /// See `TextSanitizerBuilder`
+    pub fn builder() -> TextSanitizerBuilder {
+        TextSanitizerBuilder::new()
+    }
+
     /*----------------------------------------------------------------------------
      * Administration Methods
      */
@@ -250,6 +533,31 @@ impl TextSanitizer {
         self._bprofiling = bprofiling;
     }
 
+    /// This method sets the receiver of the detailed activity messages in debug mode.\
+    /// By default they are printed to STDOUT. Without the cargo feature `std`
+    /// they are dropped unless a `DebugSink` is set.
+    ///
+    /// # Parameter:
+    ///
+    /// * `sink` - static reference to the `DebugSink`.
+    ///
+    /// # Example:
+    ///
+    /// Send the debug messages explicitly to STDOUT
+    /// ```
+    ///    use text_sanitizer::{StdoutSink, TextSanitizer};
+    ///
+    ///    static STDOUT_SINK: StdoutSink = StdoutSink;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.set_debug(true);
+    ///    sanitizer.set_debug_sink(&STDOUT_SINK);
+    /// ```
+    pub fn set_debug_sink(&mut self, sink: &'static dyn DebugSink) {
+        self._odebug_sink = Some(sink);
+    }
+
     /// This method parses the runtime options from their string presentation.\
     /// This is used for backward compatibility with the Procedural Interface.
     ///
@@ -270,28 +578,150 @@ impl TextSanitizer {
     ///    sanitizer.set_options_from_string(&"-d -q");
     /// ```
     pub fn set_options_from_string(&mut self, options: &str) {
-        //-------------------------------------
-        // Parse the Function Options
+        let mut parsed = self.get_options();
 
-        let mut sopt;
+        parsed.merge_string(options);
 
-        if (!options.is_empty()) {
-            for prm in options.split_whitespace() {
-                if prm.starts_with("--") {
-                    sopt = prm.split_at(2).1;
-                    sopt.to_lowercase();
-                } else if prm.starts_with('-') {
-                    sopt = prm.split_at(1).1;
-                    sopt.to_lowercase();
+        self.set_options(&parsed);
+    }
 
-                    match sopt {
-                        "q" | "b" => self._bquiet = true,
-                        "d" | "v" => self._bdebug = true,
-                        _ => {}
-                    } //match sopt.as_ref()
-                }
-            } //for mut prm in options.split_whitespace() {
-        } //if(!options.is_empty())
+    /// This method sets all runtime options from typed `SanitizeOptions`.
+    ///
+    /// # Example:
+    ///
+    /// Create a `TextSanitizer` object and enable debugging
+    /// ```
+    ///    use text_sanitizer::{SanitizeOptions, TextSanitizer};
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.set_options(&SanitizeOptions {
+    ///        debug: true,
+    ///        ..SanitizeOptions::default()
+    ///    });
+    /// ```
+    pub fn set_options(&mut self, options: &SanitizeOptions) {
+        self._bquiet = options.quiet;
+        self._bdebug = options.debug;
+        self._bprofiling = options.profiling;
+        self._fallback = options.fallback.clone();
+        self._charset = options.charset;
+        self._bpreserve_width = options.preserve_width;
+    }
+
+    /// This method sets how characters without any mapping are handled.
+    ///
+    /// # Example:
+    ///
+    /// Replace unmapped characters with a question mark
+    /// ```
+    ///    use text_sanitizer::{Fallback, TextSanitizer};
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.set_fallback(Fallback::Replace("?".to_string()));
+    ///
+    ///    assert_eq!(sanitizer.sanitize_string("Ausfl\u{fc}ge"), "Ausfl?ge");
+    /// ```
+    pub fn set_fallback(&mut self, fallback: Fallback) {
+        self._fallback = fallback;
+    }
+
+    /// This method sets the character set of the sanitized output.\
+    /// Characters which are valid in the target character set are not replaced.
+    ///
+    /// # Example:
+    ///
+    /// Keep all printable UTF-8 characters but clean up the broken bytes
+    /// ```
+    ///    use text_sanitizer::{TargetCharset, TextSanitizer};
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.set_target_charset(TargetCharset::PrintableUtf8);
+    ///
+    ///    assert_eq!(sanitizer.sanitize_u8(b"5\xe2\x82\xac \xfc"), "5\u{20ac} (?fc)");
+    /// ```
+    pub fn set_target_charset(&mut self, charset: TargetCharset) {
+        self._charset = charset;
+    }
+
+    /// This method enables the display-width-preserving mode for column aligned text
+    /// like tables.\
+    /// Each line keeps the display width of the source. A replacement with the same width
+    /// from another requested `LanguageMap` is preferred. Otherwise the whitespace after
+    /// the replacement is trimmed or padded. At least one space is always kept.
+    ///
+    /// # Example:
+    ///
+    /// Keep the columns of a table aligned
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.set_preserve_width(true);
+    ///    sanitizer.add_request_language(&"de");
+    ///
+    ///    let stable = "Gr\u{fc}n   | 1\nRot    | 2\n";
+    ///
+    ///    assert_eq!(sanitizer.sanitize_string(stable), "Gruen  | 1\nRot    | 2\n");
+    /// ```
+    pub fn set_preserve_width(&mut self, bpreserve: bool) {
+        self._bpreserve_width = bpreserve;
+    }
+
+    /// This method adds a single character to the allowlist.\
+    /// Characters on the allowlist are not looked up in the `LanguageMap`s
+    /// and are passed through unchanged.
+    ///
+    /// # Example:
+    ///
+    /// Keep the Euro Sign even for plain ASCII output
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///    sanitizer.add_keep_character('\u{20ac}');
+    ///
+    ///    assert_eq!(sanitizer.sanitize_string("5\u{20ac} \u{2764}"), "5\u{20ac} <3");
+    /// ```
+    pub fn add_keep_character(&mut self, c: char) {
+        self.add_keep_range(c, c);
+    }
+
+    /// This method adds a range of characters to the allowlist.
+    /// Both characters are part of the range.
+    ///
+    /// # Example:
+    ///
+    /// Keep the Greek letters
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_keep_range('\u{391}', '\u{3c9}');
+    ///
+    ///    assert_eq!(sanitizer.sanitize_string("\u{3b1} = 5\u{b0}"), "\u{3b1} = 5(?b0)");
+    /// ```
+    pub fn add_keep_range(&mut self, first: char, last: char) {
+        let range = if first <= last {
+            (first, last)
+        } else {
+            (last, first)
+        };
+
+        if !self._vkeeprngs.contains(&range) {
+            self._vkeeprngs.push(range);
+        }
+    }
+
+    /// This method removes all characters from the allowlist.
+    pub fn clear_keep_characters(&mut self) {
+        self._vkeeprngs.clear();
     }
 
     /// This method allows to replace the default `ConversionMap` with a custom one.
@@ -305,8 +735,8 @@ impl TextSanitizer {
     ///
     ///    let mut sanitizer = TextSanitizer::new();
     ///
-    ///    let mut conv_map = ConversionMap(HashMap::with_capacity(2));
-    ///    let mut lang_map = LanguageMap(HashMap::with_capacity(4));
+    ///    let mut conv_map = ConversionMap(HashMap::new());
+    ///    let mut lang_map = LanguageMap(HashMap::new());
     ///
     ///    lang_map.0.insert("d".to_string(), "".to_string());
     ///    lang_map.0.insert("1b".to_string(), "".to_string());
@@ -334,7 +764,7 @@ impl TextSanitizer {
     ///
     ///    let mut sanitizer = TextSanitizer::new();
     ///
-    ///    let mut lang_map = LanguageMap(HashMap::with_capacity(4));
+    ///    let mut lang_map = LanguageMap(HashMap::new());
     ///
     ///    lang_map.0.insert("d".to_string(), "".to_string());
     ///    lang_map.0.insert("1b".to_string(), "".to_string());
@@ -432,11 +862,54 @@ impl TextSanitizer {
         self._vrqlangs.clear();
     }
 
-    #[doc(hidden)]
-    fn init(&mut self) {
-        self._oconv_map = Some(ConversionMap(HashMap::with_capacity(3)));
+    /// Adds the languages of locale names like "es_ES.UTF-8" in their order.\
+    /// Languages without a `LanguageMap` are skipped. "en" is moved to the end
+    /// as the last fallback. Returns the added languages without "en".
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///
+    ///    assert_eq!(sanitizer.add_locale_languages(&["es_ES.UTF-8", "fi_FI"]), vec!["es"]);
+    ///    assert_eq!(sanitizer.sanitize_string("Espa\u{f1}a \u{2764}"), "Espana <3");
+    /// ```
+    pub fn add_locale_languages(&mut self, locales: &[&str]) -> Vec<String> {
+        let mut vlangs = Vec::new();
 
-        let mut lngrplmap = LanguageMap(HashMap::with_capacity(12));
+        for slang in build_locale_languages(locales) {
+            if slang != "en" && self.has_language_map(&slang) {
+                self.add_request_language(&slang);
+                vlangs.push(slang);
+            }
+        }
+
+        self._vrqlangs.retain(|slang| slang != "en");
+        self._vrqlangs.push(String::from("en"));
+
+        vlangs
+    }
+
+    /// Adds the languages of the GNU list "LANGUAGE" and of the first environment variable
+    /// of "LC_ALL", "LC_MESSAGES" and "LANG" which is set.\
+    /// See `TextSanitizer::add_locale_languages()`
+    #[cfg(feature = "std")]
+    pub fn add_system_languages(&mut self) -> Vec<String> {
+        let vlangs = find_locale_languages();
+        let vlocales: Vec<&str> = vlangs.iter().map(|slang| slang.as_str()).collect();
+
+        self.add_locale_languages(&vlocales)
+    }
+
+    #[doc(hidden)]
+    fn init(&mut self) {
+        self._oconv_map = Some(ConversionMap(HashMap::new()));
+
+        let mut lngrplmap = LanguageMap(HashMap::new());
 
         lngrplmap.0.insert("d".to_string(), "".to_string());
         lngrplmap.0.insert("1b".to_string(), "".to_string());
@@ -455,7 +928,7 @@ impl TextSanitizer {
             conv_map.0.insert("en".to_string(), lngrplmap);
         }
 
-        let mut lngrplmap = LanguageMap(HashMap::with_capacity(5));
+        let mut lngrplmap = LanguageMap(HashMap::new());
 
         lngrplmap.0.insert("df".to_string(), "ss".to_string());
         lngrplmap.0.insert("dc".to_string(), "Ue".to_string());
@@ -467,7 +940,7 @@ impl TextSanitizer {
             conv_map.0.insert("de".to_string(), lngrplmap);
         }
 
-        let mut lngrplmap = LanguageMap(HashMap::with_capacity(6));
+        let mut lngrplmap = LanguageMap(HashMap::new());
 
         lngrplmap.0.insert("d3".to_string(), "O".to_string());
         lngrplmap.0.insert("e1".to_string(), "a".to_string());
@@ -481,6 +954,17 @@ impl TextSanitizer {
         }
     }
 
+    #[doc(hidden)]
+    // Writes a debug message to the DebugSink or to STDOUT
+    fn write_debug(&self, message: fmt::Arguments) {
+        if let Some(sink) = self._odebug_sink {
+            sink.write_debug(message);
+        } else {
+            #[cfg(feature = "std")]
+            print!("{}", message);
+        }
+    }
+
     #[doc(hidden)]
     fn from_utf8_lossy(mut input: &[u8]) -> String {
         let mut sanitized = String::with_capacity(input.len());
@@ -492,7 +976,7 @@ impl TextSanitizer {
             //            &input
             //        );
 
-            match std::str::from_utf8(input) {
+            match str::from_utf8(input) {
                 Ok(valid) => {
                     unsafe {
                         for c in valid.chars() {
@@ -512,7 +996,7 @@ impl TextSanitizer {
                     let (valid, after_valid) = input.split_at(error.valid_up_to());
 
                     unsafe {
-                        let spec = std::str::from_utf8_unchecked(valid);
+                        let spec = str::from_utf8_unchecked(valid);
 
                         for c in spec.chars() {
                             if c.is_ascii() {
@@ -555,7 +1039,8 @@ impl TextSanitizer {
         let mut bprsgo: bool = true;
 
         if (self._bdebug && !self._bquiet) {
-            println!(
+            debug_println!(
+                self,
                 "; sequence 0 (cnt: '{}', strt: '{}', end: '{}'): '{:x?}' - parsing ...",
                 sequence.len(),
                 icstrt,
@@ -566,7 +1051,8 @@ impl TextSanitizer {
 
         while bprsgo && icstrt < icend {
             if (self._bdebug && !self._bquiet) {
-                println!(
+                debug_println!(
+                    self,
                     "; sequence (cnt: '{}', strt: '{}', end: '{}'): '{:x?}' - parsing ...",
                     sequence[icstrt..icend].len(),
                     icstrt,
@@ -583,34 +1069,38 @@ impl TextSanitizer {
                     build_result.push(s.to_owned());
 
                     if (self._bdebug && !self._bquiet) {
-                        println!("utf8 ok: '{}'", s);
+                        debug_println!(self, "utf8 ok: '{}'", s);
                     }
                 }
                 Err(e) => {
                     if (self._bdebug && !self._bquiet) {
-                        println!("utf8 Err: '{:?}'", e);
+                        debug_println!(self, "utf8 Err: '{:?}'", e);
                     }
 
                     ivldps = e.valid_up_to();
 
                     if (self._bdebug && !self._bquiet) {
-                        println!("vld ps: '{}'", ivldps);
+                        debug_println!(self, "vld ps: '{}'", ivldps);
                     }
 
                     ivldps += icstrt;
 
                     if (self._bdebug && !self._bquiet) {
-                        println!("vld idx: '{}'", ivldps);
+                        debug_println!(self, "vld idx: '{}'", ivldps);
                     }
 
                     if ivldps > icstrt {
                         if (self._bdebug && !self._bquiet) {
-                            println!("utf8 recovered: '{:x?}'", &sequence[icstrt..ivldps]);
+                            debug_println!(
+                                self,
+                                "utf8 recovered: '{:x?}'",
+                                &sequence[icstrt..ivldps]
+                            );
                         }
 
                         unsafe {
                             build_result.push(
-                                std::str::from_utf8_unchecked(&sequence[icstrt..ivldps]).to_owned(),
+                                str::from_utf8_unchecked(&sequence[icstrt..ivldps]).to_owned(),
                             );
                         }
 
@@ -621,12 +1111,12 @@ impl TextSanitizer {
 
                     if let Some(invalid_sequence_length) = e.error_len() {
                         if (self._bdebug && !self._bquiet) {
-                            println!("ivld chrs cnt: '{}'", invalid_sequence_length);
+                            debug_println!(self, "ivld chrs cnt: '{}'", invalid_sequence_length);
                         }
 
                         for iu in (ivldps)..(ivldps + invalid_sequence_length) {
                             if (self._bdebug && !self._bquiet) {
-                                println!("ivld chr: '{:x?}'", &sequence[iu]);
+                                debug_println!(self, "ivld chr: '{:x?}'", &sequence[iu]);
                             }
 
                             build_result.push(format!("(?{:x?})", &sequence[iu]));
@@ -636,12 +1126,12 @@ impl TextSanitizer {
                     } else {
                         //All Bytes are invalid
                         if (self._bdebug && !self._bquiet) {
-                            println!("ivld chrs cnt: all");
+                            debug_println!(self, "ivld chrs cnt: all");
                         }
 
                         for iu in (ivldps)..(icend) {
                             if (self._bdebug && !self._bquiet) {
-                                println!("ivld chr: '{:x?}'", &sequence[iu]);
+                                debug_println!(self, "ivld chr: '{:x?}'", &sequence[iu]);
                             }
 
                             build_result.push(format!("(?{:x?})", &sequence[iu]));
@@ -657,311 +1147,1307 @@ impl TextSanitizer {
     }
 
     #[doc(hidden)]
-    // A sequence of bytes is parsed into multiple characters or ascii symbols
-    fn parse_unicode(&self, sequence: &[u8]) -> Vec<String> {
-        let mut parse_result: Vec<String> = Vec::new();
+    // A sequence of bytes is parsed into multiple codepoints or invalid bytes
+    // Valid characters are returned together with their codepoint
+    fn parse_unicode(&self, sequence: &[u8]) -> Vec<(String, Option<char>)> {
+        let mut parse_result: Vec<(String, Option<char>)> = Vec::new();
 
         let vunicode = self.build_unicode(&sequence);
 
-        if (self._bdebug && !self._bquiet) {
-            println!("uni res: '{:?}'", vunicode);
-        }
+        if (self._bdebug && !self._bquiet) {
+            debug_println!(self, "uni res: '{:?}'", vunicode);
+        }
+
+        if !vunicode.is_empty() {
+            for sv in vunicode {
+                if sv.starts_with("(?") && sv.ends_with(')') {
+                    parse_result.push((sv[2..(sv.len() - 1)].to_owned(), None));
+                } else {
+                    for c in sv.chars() {
+                        let suni = c.escape_unicode().to_string();
+
+                        parse_result.push((suni[3..(suni.len() - 1)].to_owned(), Some(c)));
+                    } // for c in sv.chars()
+                } //if sv.starts_with("(?") && sv.ends_with(')')
+            } //for sv in vunicode
+        } //if ! vunicode.is_empty()
+
+        parse_result
+    }
+
+    /// Parses the given reference to raw text data as array of bytes `u8` into
+    /// a new valid `std::str::String`.
+    ///
+    /// # Parameters:
+    ///
+    /// * `text` - raw text data as array of bytes `u8`
+    ///
+    /// # Examples:
+    ///
+    /// Test data is the Sparkle Heart from the UTF-8 documentation examples but it is broken.\
+    /// According to the Official Standard Library Documentation at:\
+    /// [std::string::String::from_utf8()](https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8)\
+    /// this would produce a `FromUtf8Error` or **panic** the application
+    /// when used with `unwrap()`
+    /// ```
+    ///    //-------------------------------------
+    ///    // Test data is the Sparkle Heart from the UTF-8 documentation examples but it is broken
+    ///
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let vsparkle_heart = vec![240, 159, 119, 150];
+    ///
+    ///    let mut sanitizer = TextSanitizer::new_with_options(false, true, false);
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///
+    ///    let srsout = sanitizer.sanitize_u8(&vsparkle_heart);
+    ///
+    ///    println!("sparkle_heart: '{}'", srsout);
+    ///
+    ///    assert_eq!(srsout, "(?f0)(?9f)w(?96)");
+    /// ```
+    pub fn sanitize_u8(&self, text: &[u8]) -> String {
+        self.sanitize_width_u8(text, self._bpreserve_width)
+    }
+
+    #[doc(hidden)]
+    // Sanitizes the raw text data with or without preserving the Display Width
+    // The structured formats sanitize their values without preserving the Display Width
+    fn sanitize_width_u8(&self, text: &[u8], bpreserve_width: bool) -> String {
+        if (self._bdebug && !self._bquiet) {
+            debug_println!(self, "vtext 0:'{:?}'", text);
+        }
+
+        if let Some(conv_map) = &self._oconv_map {
+            let mut srstxt = String::with_capacity(text.len());
+            let mut srptchrs = String::new();
+            let itxtlen = text.len();
+            let mut ic: usize = 0;
+            //Display Width of the current Line compared to the Source
+            let mut iwdelta: isize = 0;
+
+            while ic < itxtlen {
+                //------------------------
+                //Valid ASCII Characters
+
+                let iascend = scan_plain_ascii(text, ic);
+
+                if iascend > ic {
+                    if (self._bdebug && !self._bquiet) {
+                        for (iu, uc) in text[ic..iascend].iter().enumerate() {
+                            srptchrs.push_str(&format!(
+                                "; {} - {}:'{}'",
+                                ic + iu,
+                                uc,
+                                char::from(*uc)
+                            ));
+                        }
+                    } //if(bdebug && ! bquiet)
+
+                    if bpreserve_width && iwdelta != 0 {
+                        self.push_aligned_ascii(&text[ic..iascend], &mut srstxt, &mut iwdelta);
+                    } else {
+                        //Add the whole run of valid ASCII Characters at once
+                        unsafe {
+                            srstxt.push_str(str::from_utf8_unchecked(&text[ic..iascend]));
+                        }
+                    }
+
+                    ic = iascend;
+                } //if iascend > ic
+
+                if ic < itxtlen {
+                    //------------------------
+                    //Non ASCII Characters
+
+                    let ispcend = scan_special(text, ic);
+
+                    if (self._bdebug && !self._bquiet) {
+                        for (iu, uc) in text[ic..ispcend].iter().enumerate() {
+                            srptchrs.push_str(&format!(
+                                "; {} - {}:'{}' - non-ascii '{} - {}'|",
+                                ic + iu,
+                                uc,
+                                char::from(*uc),
+                                ic,
+                                ispcend
+                            ));
+                        }
+
+                        debug_println!(
+                            self,
+                            "pdg spec chars '{} - {}': '{:?}'",
+                            ic,
+                            ispcend,
+                            &text[ic..ispcend]
+                        );
+                    } //if(bdebug && ! bquiet)
+
+                    //Parse the slice of Non ASCII Characters
+                    iwdelta += self.replace_sequence(
+                        conv_map,
+                        &text[ic..ispcend],
+                        &mut srstxt,
+                        bpreserve_width,
+                    );
+
+                    ic = ispcend;
+                } //if ic < itxtlen
+            } //while ic < itxtlen
+
+            if bpreserve_width && iwdelta < 0 {
+                //Pad the last Line
+                srstxt.push_str(&" ".repeat(-iwdelta as usize));
+            }
+
+            if (self._bdebug && !self._bquiet) {
+                srptchrs.push_str(&format!("; chr cnt '{}'", ic));
+
+                debug_println!(self, "; sanitze done.");
+                debug_println!(self, "chrs rpt: '{:?}'", &srptchrs);
+
+                let vsttrpt: Vec<char> = String::from_utf8_lossy(text).to_mut().chars().collect();
+
+                debug_println!(
+                    self,
+                    "stt rpt chrs (count : '{}'):\n{:?}",
+                    vsttrpt.len(),
+                    vsttrpt
+                );
+
+                debug_println!(self, "stt chrs ascii:");
+
+                for c in &vsttrpt {
+                    if !c.is_ascii() {
+                        debug_print!(self, "{}|", c.escape_unicode().to_string());
+                    } else {
+                        debug_print!(self, "{}|", c);
+                    }
+                } //for c in &vsttrpt
+
+                debug_println!(self);
+            } //if(bdbg && ! bqt)
+
+            //Return the sanitized String
+            srstxt
+        } else {
+            String::from_utf8_lossy(text).into_owned()
+        }
+    }
+
+    #[doc(hidden)]
+    // Adds plain ASCII Characters while balancing the Display Width of the Line
+    // by trimming or padding the runs of spaces
+    fn push_aligned_ascii(&self, run: &[u8], srstxt: &mut String, iwdelta: &mut isize) {
+        for (iu, uc) in run.iter().enumerate() {
+            if *iwdelta == 0 {
+                //The Line is balanced again
+                unsafe {
+                    srstxt.push_str(str::from_utf8_unchecked(&run[iu..]));
+                }
+
+                break;
+            }
+
+            match uc {
+                b'\n' => {
+                    if *iwdelta < 0 {
+                        srstxt.push_str(&" ".repeat(-*iwdelta as usize));
+                    }
+
+                    *iwdelta = 0;
+                    srstxt.push('\n');
+                }
+                b' ' => {
+                    if *iwdelta > 0 && srstxt.ends_with(' ') {
+                        //Trim the Run of Spaces
+                        *iwdelta -= 1;
+                    } else {
+                        srstxt.push(' ');
+
+                        if *iwdelta < 0 {
+                            //Pad the Run of Spaces
+                            srstxt.push_str(&" ".repeat(-*iwdelta as usize));
+                            *iwdelta = 0;
+                        }
+                    }
+                }
+                _ => srstxt.push(char::from(*uc)),
+            } //match uc
+        } //for (iu, uc) in run.iter().enumerate()
+    }
+
+    #[doc(hidden)]
+    // Looks up the replacement in the requested languages by their priority.
+    // A replacement of the given display width is preferred.
+    fn find_replacement<'a>(
+        &self,
+        conv_map: &'a ConversionMap,
+        suni: &str,
+        owidth: Option<usize>,
+    ) -> Option<&'a String> {
+        let mut orpl = None;
+
+        for slng in &self._vrqlangs {
+            if let Some(lngmap) = conv_map.0.get(slng.as_str()) {
+                if let Some(rpl) = lngmap.0.get(suni) {
+                    match owidth {
+                        Some(iwidth) => {
+                            if str_width(rpl) == iwidth {
+                                return Some(rpl);
+                            }
+
+                            if orpl.is_none() {
+                                orpl = Some(rpl);
+                            }
+                        }
+                        None => return Some(rpl),
+                    } //match owidth
+                }
+            } //if let Some(lngmap) = conv_map.0.get(slng.as_str())
+        } //for slng in &self._vrqlangs
+
+        orpl
+    }
+
+    #[doc(hidden)]
+    // Replaces a sequence of Non ASCII bytes with the mappings of the requested languages
+    // It returns how much wider the replacement is displayed than the sequence
+    fn replace_sequence(
+        &self,
+        conv_map: &ConversionMap,
+        sequence: &[u8],
+        srstxt: &mut String,
+        bpreserve_width: bool,
+    ) -> isize {
+        let vuni = self.parse_unicode(sequence);
+        let mut iwdelta: isize = 0;
+
+        if (self._bdebug && !self._bquiet) {
+            debug_print!(self, "= {:?}", vuni);
+        }
+
+        for (suni, ochr) in vuni {
+            //Characters of the Target Charset and of the Allowlist are kept
+            if let Some(c) = ochr {
+                if self._charset.contains(c) || self.is_keep_character(c) {
+                    srstxt.push(c);
+
+                    if (self._bdebug && !self._bquiet) {
+                        debug_print!(self, " -> '{}' ({:?})", c, &self._charset);
+                    }
+
+                    continue;
+                }
+            } //if let Some(c) = ochr
+
+            //Invalid Bytes are displayed as one Replacement Character
+            let isrcwidth = match ochr {
+                Some(c) => char_width(c),
+                None => 1,
+            };
+            let irslen = srstxt.len();
+
+            let orpl = if bpreserve_width {
+                self.find_replacement(conv_map, &suni, Some(isrcwidth))
+            } else {
+                self.find_replacement(conv_map, &suni, None)
+            };
+
+            match orpl {
+                Some(rpl) => {
+                    srstxt.push_str(rpl);
+
+                    if (self._bdebug && !self._bquiet) {
+                        debug_print!(self, " -> '{}'", rpl);
+                    }
+                }
+                None => {
+                    match &self._fallback {
+                        Fallback::Codepoint => srstxt.push_str(&format!("(?{})", &suni)),
+                        Fallback::Remove => {}
+                        Fallback::Replace(srpl) => srstxt.push_str(srpl),
+                    }
+
+                    if (self._bdebug && !self._bquiet) {
+                        debug_print!(self, " -> '(?{})' ({:?})", &suni, &self._fallback);
+                    }
+                } //Some(rpl)
+            } //match orpl
+
+            iwdelta += str_width(&srstxt[irslen..]) as isize - isrcwidth as isize;
+        } //for suni in vuni
+
+        if (self._bdebug && !self._bquiet) {
+            debug_println!(self, "'");
+        } //if(bdbg && ! bqt)
+
+        iwdelta
+    }
+
+    /// Creates from a given string slice a simplified version with ASCII characters.
+    ///
+    /// # Parameters:
+    ///
+    /// * `text` - String slice of text to sanitize
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///    //-------------------------------------
+    ///    // Test data is the Sparkle Heart from the UTF-8 documentation examples.
+    ///    // It will be sanitized into the ASCII Characters " <3 "
+    ///
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let vsparkle_heart = vec![240, 159, 146, 150];
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///
+    ///    let srsout = match std::str::from_utf8(&vsparkle_heart) {
+    ///         Ok(s) => sanitizer.sanitize_string(s),
+    ///         Err(_) => sanitizer.sanitize_u8(&vsparkle_heart),
+    ///    };
+    ///
+    ///    println!("sparkle_heart: '{}'", srsout);
+    ///
+    ///    assert_eq!(srsout, "<3");
+    /// ```
+    pub fn sanitize_string(&self, text: &str) -> String {
+        self.sanitize_u8(text.as_bytes())
+    }
+
+    /// Sanitizes the text of a reader line by line.\
+    /// The iterator yields the line number starting at 1, the sanitized line without
+    /// its line ending and whether any character of the line was replaced.
+    /// Each line is sanitized on its own. So an invalid byte at the end of a line
+    /// never affects the next line.
+    ///
+    /// This method is only available with the cargo feature `std`.
+    ///
+    /// # Parameters:
+    ///
+    /// * `reader` - source of the raw text data like a `BufReader` of a file
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"de");
+    ///
+    ///    let vlog = b"Start\nGr\xc3\xbc\xc3\x9fe \xc3\n";
+    ///
+    ///    for line in sanitizer.sanitize_lines(&vlog[..]) {
+    ///        let (iline, sline, breplaced) = line.expect("line not readable");
+    ///
+    ///        if breplaced {
+    ///            assert_eq!((iline, sline.as_str()), (2, "Gruesse (?c3)"));
+    ///        }
+    ///    }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn sanitize_lines<R: BufRead>(&self, reader: R) -> SanitizedLines<'_, R> {
+        SanitizedLines::new(self, reader)
+    }
+
+    /// Sanitizes the text of a reader line by line and passes each line to a callback.\
+    /// The callback receives the line number starting at 1, the sanitized line without
+    /// its line ending and whether any character of the line was replaced.\
+    /// Returns the number of lines or the first read error.
+    /// See `TextSanitizer::sanitize_lines()`
+    ///
+    /// This method is only available with the cargo feature `std`.
+    #[cfg(feature = "std")]
+    pub fn sanitize_lines_with<R, F>(&self, reader: R, mut callback: F) -> io::Result<usize>
+    where
+        R: BufRead,
+        F: FnMut(usize, &str, bool),
+    {
+        let mut ilines = 0;
+
+        for line in self.sanitize_lines(reader) {
+            let (iline, sline, breplaced) = line?;
+
+            callback(iline, &sline, breplaced);
+            ilines = iline;
+        }
+
+        Ok(ilines)
+    }
+
+    /// Parses the given raw HTML data into a new valid `std::str::String`.\
+    /// Character references like "&auml;", "&#252;" or "&#xfc;" are decoded
+    /// and replaced like the same characters written as raw bytes.
+    /// References to markup characters like "&lt;" or "&amp;" and the non-breaking space
+    /// stay escaped. Tags and attributes are kept intact and replacements with markup
+    /// characters are escaped.\
+    /// Comments and the content of "script" and "style" elements are sanitized
+    /// without decoding. The display width is not preserved for HTML.
+    ///
+    /// # Parameters:
+    ///
+    /// * `text` - raw HTML data as array of bytes `u8`
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///    sanitizer.add_request_language(&"de");
+    ///
+    ///    let shtml = "<b title=\"Gr&uuml;&szlig;e\">&Uuml;ber &lt;5&euro; \u{2764}</b>";
+    ///
+    ///    assert_eq!(
+    ///        sanitizer.sanitize_html_u8(shtml.as_bytes()),
+    ///        "<b title=\"Gruesse\">Ueber &lt;5EUR &lt;3</b>"
+    ///    );
+    /// ```
+    pub fn sanitize_html_u8(&self, text: &[u8]) -> String {
+        //The Markup is not column aligned and each Replacement is sanitized on its own
+        sanitize_html(text, |run| self.sanitize_width_u8(run, false))
+    }
+
+    /// Creates from a given HTML string slice a simplified version with ASCII characters.\
+    /// See `TextSanitizer::sanitize_html_u8()`
+    pub fn sanitize_html_string(&self, text: &str) -> String {
+        self.sanitize_html_u8(text.as_bytes())
+    }
+
+    /// Parses the given raw CSV or TSV data and sanitizes each field on its own.\
+    /// Quoted fields are unquoted before they are sanitized and quoted again afterwards.
+    /// Fields which contain the delimiter, the quote or line breaks now are quoted as well.
+    /// Replacements never add delimiters or line breaks. These become spaces.
+    /// So the record structure stays intact.\
+    /// Fields of columns which are not selected in `options` are kept as in the source.
+    /// The display width is not preserved for CSV.
+    ///
+    /// # Parameters:
+    ///
+    /// * `text` - raw CSV or TSV data as array of bytes `u8`
+    /// * `options` - format of the data and the sanitized columns
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///    use text_sanitizer::{CsvOptions, TextSanitizer};
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///    sanitizer.add_request_language(&"de");
+    ///
+    ///    let scsv = "host,owner\nsrv1,\"M\u{fc}ller, J\u{f6}rg\"\nsrv2,\u{ab}IT\u{bb}\n";
+    ///
+    ///    assert_eq!(
+    ///        sanitizer.sanitize_csv_u8(scsv.as_bytes(), &CsvOptions::csv()),
+    ///        "host,owner\nsrv1,\"Mueller, Joerg\"\nsrv2,\"\"\"IT\"\"\"\n"
+    ///    );
+    /// ```
+    pub fn sanitize_csv_u8(&self, text: &[u8], options: &CsvOptions) -> String {
+        //The Fields are not column aligned and each Replacement is sanitized on its own
+        sanitize_csv(text, options, |run| self.sanitize_width_u8(run, false))
+    }
+
+    /// Parses the given CSV or TSV string slice and sanitizes each field on its own.\
+    /// See `TextSanitizer::sanitize_csv_u8()`
+    pub fn sanitize_csv_string(&self, text: &str, options: &CsvOptions) -> String {
+        self.sanitize_csv_u8(text.as_bytes(), options)
+    }
+
+    /// Parses the given raw JSON data and sanitizes its string values.\
+    /// Escape sequences like "\u00ed" are decoded and replaced like the same characters
+    /// written as raw bytes. The sanitized strings are escaped again. So the result is
+    /// valid JSON. The whitespace, numbers and literals of the document are kept unchanged.
+    /// The display width is not preserved for JSON.
+    ///
+    /// # Parameters:
+    ///
+    /// * `text` - raw JSON data as array of bytes `u8`
+    /// * `bkeys` - whether the keys of the objects are sanitized as well
+    ///
+    /// # Errors:
+    ///
+    /// Returns a `JsonError` with the position when `text` is not a valid JSON document.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"es");
+    ///
+    ///    let sjson = "{\"stdout_lines\": [\"Acci\\u00f3n\", \"D\u{ed}a\"], \"rc\": 0}";
+    ///
+    ///    assert_eq!(
+    ///        sanitizer.sanitize_json_u8(sjson.as_bytes(), false),
+    ///        Ok(String::from("{\"stdout_lines\": [\"Accion\", \"Dia\"], \"rc\": 0}"))
+    ///    );
+    ///    assert!(sanitizer.sanitize_json_u8(b"{\"rc\": }", false).is_err());
+    /// ```
+    pub fn sanitize_json_u8(&self, text: &[u8], bkeys: bool) -> Result<String, JsonError> {
+        //The Strings are not column aligned and each Replacement is sanitized on its own
+        sanitize_json(text, bkeys, |string| self.sanitize_width_u8(string, false))
+    }
+
+    /// Parses the given JSON string slice and sanitizes its string values.\
+    /// See `TextSanitizer::sanitize_json_u8()`
+    pub fn sanitize_json_string(&self, text: &str, bkeys: bool) -> Result<String, JsonError> {
+        self.sanitize_json_u8(text.as_bytes(), bkeys)
+    }
+
+    /// Sanitizes large raw text data on all available cores.\
+    /// The text is split into chunks at safe boundaries. Those are newlines
+    /// or plain ASCII characters which can never be part of a multi-byte sequence.
+    /// The chunks are sanitized in parallel and joined in their original order.
+    /// So the result is the same as with `sanitize_u8()`.
+    ///
+    /// This method is only available with the cargo feature `parallel`.
+    ///
+    /// # Parameters:
+    ///
+    /// * `text` - raw text data as array of bytes `u8`
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let vtext = "Ausfl\u{fc}ge mit \u{2764}\n".repeat(10000);
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///    sanitizer.add_request_language(&"de");
+    ///
+    ///    let srsout = sanitizer.sanitize_u8_parallel(vtext.as_bytes());
+    ///
+    ///    assert_eq!(srsout, "Ausfluege mit <3\n".repeat(10000));
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn sanitize_u8_parallel(&self, text: &[u8]) -> String {
+        let ichunksize = text.len() / (rayon::current_num_threads() * 4);
+        let ichunksize = if ichunksize > PARALLEL_CHUNK_MIN {
+            ichunksize
+        } else {
+            PARALLEL_CHUNK_MIN
+        };
+
+        if text.len() <= ichunksize {
+            return self.sanitize_u8(text);
+        }
+
+        let mut vchunks: Vec<&[u8]> = Vec::with_capacity(text.len() / ichunksize + 1);
+        let mut ic: usize = 0;
+
+        //The Display Width is balanced per Line so Chunks must end at a Newline
+        let iwindow = if self._bpreserve_width {
+            text.len()
+        } else {
+            ichunksize / 8
+        };
+
+        while ic < text.len() {
+            let ichunkend = find_safe_boundary(text, ic + ichunksize, iwindow);
+
+            vchunks.push(&text[ic..ichunkend]);
+
+            ic = ichunkend;
+        } //while ic < text.len()
+
+        if (self._bdebug && !self._bquiet) {
+            debug_println!(
+                self,
+                "; parallel chunks (cnt: '{}', size: '{}')",
+                vchunks.len(),
+                ichunksize
+            );
+        }
+
+        let vsanitized: Vec<String> = vchunks
+            .par_iter()
+            .map(|chunk| self.sanitize_u8(chunk))
+            .collect();
+
+        let mut srstxt = String::with_capacity(vsanitized.iter().map(|s| s.len()).sum());
+
+        for sanitized in &vsanitized {
+            srstxt.push_str(sanitized);
+        }
+
+        //Return the sanitized String
+        srstxt
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_quiet(&self) -> bool {
+        self._bquiet
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self._bdebug
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self._bprofiling
+    }
+
+    pub fn has_request_language(&self, slanguage: &str) -> bool {
+        self._vrqlangs.contains(&String::from(slanguage))
+    }
+
+    /// Checks whether the `ConversionMap` holds a `LanguageMap` for the language shortcode.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let sanitizer = TextSanitizer::new();
+    ///
+    ///    assert!(sanitizer.has_language_map(&"de"));
+    ///    assert!(!sanitizer.has_language_map(&"deu"));
+    /// ```
+    pub fn has_language_map(&self, language: &str) -> bool {
+        match &self._oconv_map {
+            Some(map) => map.0.contains_key(language),
+            None => false,
+        }
+    }
+
+    /// Returns the language shortcodes of all `LanguageMap`s in alphabetical order.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let sanitizer = TextSanitizer::new();
+    ///
+    ///    assert_eq!(sanitizer.get_languages(), vec!["de", "en", "es"]);
+    /// ```
+    pub fn get_languages(&self) -> Vec<String> {
+        let mut vlangs: Vec<String> = match &self._oconv_map {
+            Some(map) => map.0.keys().cloned().collect(),
+            None => Vec::new(),
+        };
+
+        vlangs.sort();
+
+        vlangs
+    }
+
+    pub fn get_fallback(&self) -> &Fallback {
+        &self._fallback
+    }
+
+    pub fn get_target_charset(&self) -> TargetCharset {
+        self._charset
+    }
+
+    pub fn is_preserve_width(&self) -> bool {
+        self._bpreserve_width
+    }
+
+    /// Checks whether a character is on the allowlist.
+    pub fn is_keep_character(&self, c: char) -> bool {
+        self._vkeeprngs
+            .iter()
+            .any(|(first, last)| *first <= c && c <= *last)
+    }
+
+    /// Checks whether a character is passed through or has a replacement
+    /// in one of the requested `LanguageMap`s.\
+    /// Characters without a replacement are handled by the `Fallback`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"de");
+    ///
+    ///    assert!(sanitizer.is_mapped_character('\u{fc}'));
+    ///    assert!(!sanitizer.is_mapped_character('\u{2764}'));
+    /// ```
+    pub fn is_mapped_character(&self, c: char) -> bool {
+        if c == '\n' || c == '\t' || self._charset.contains(c) || self.is_keep_character(c) {
+            return true;
+        }
+
+        match &self._oconv_map {
+            Some(conv_map) => self
+                .find_replacement(conv_map, &format!("{:x}", c as u32), None)
+                .is_some(),
+            None => false,
+        }
+    }
+
+    /// Finds the next character at or after `start` which is not plain ASCII
+    /// or the next invalid byte.\
+    /// It returns the position, the length in bytes and the character.
+    /// An invalid byte has the length 1 and no character.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let sanitizer = TextSanitizer::new();
+    ///
+    ///    assert_eq!(sanitizer.find_next_unit(b"Gr\xc3\xbcn \xfc", 0), Some((2, 2, Some('\u{fc}'))));
+    ///    assert_eq!(sanitizer.find_next_unit(b"Gr\xc3\xbcn \xfc", 4), Some((6, 1, None)));
+    ///    assert_eq!(sanitizer.find_next_unit(b"Gruen", 0), None);
+    /// ```
+    pub fn find_next_unit(
+        &self,
+        text: &[u8],
+        start: usize,
+    ) -> Option<(usize, usize, Option<char>)> {
+        let iunit = scan_plain_ascii(text, start);
+
+        if iunit >= text.len() {
+            return None;
+        }
+
+        let iend = core::cmp::min(iunit + 4, text.len());
+        let ivalid = match str::from_utf8(&text[iunit..iend]) {
+            Ok(_) => iend - iunit,
+            Err(e) => e.valid_up_to(),
+        };
+
+        //The valid Part is not parsed again
+        match unsafe { str::from_utf8_unchecked(&text[iunit..(iunit + ivalid)]) }
+            .chars()
+            .next()
+        {
+            Some(c) => Some((iunit, c.len_utf8(), Some(c))),
+            None => Some((iunit, 1, None)),
+        }
+    }
+
+    /// Finds the first character or invalid byte which is not passed through and has
+    /// no replacement in the requested `LanguageMap`s.\
+    /// Invalid bytes are rescued by a mapping of their value like "80".
+    /// It returns the position and the character. An invalid byte has no character.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"en");
+    ///
+    ///    assert_eq!(sanitizer.find_unmapped(b"5\x80 \xe2\x9d\xa4"), None);
+    ///    assert_eq!(sanitizer.find_unmapped(b"5\x80 \xc3\xb1"), Some((3, Some('\u{f1}'))));
+    ///    assert_eq!(sanitizer.find_unmapped(b"5 \xfa"), Some((2, None)));
+    /// ```
+    pub fn find_unmapped(&self, text: &[u8]) -> Option<(usize, Option<char>)> {
+        let mut ic = 0;
+
+        while let Some((iunit, iunitlen, ochr)) = self.find_next_unit(text, ic) {
+            let bmapped = match ochr {
+                Some(c) => self.is_mapped_character(c),
+                None => match &self._oconv_map {
+                    Some(conv_map) => self
+                        .find_replacement(conv_map, &format!("{:x}", text[iunit]), None)
+                        .is_some(),
+                    None => false,
+                },
+            };
+
+            if !bmapped {
+                return Some((iunit, ochr));
+            }
+
+            ic = iunit + iunitlen;
+        } //while let Some((iunit, iunitlen, ochr)) = self.find_next_unit(text, ic)
+
+        None
+    }
+
+    /// Returns the current runtime options as typed `SanitizeOptions`.
+    pub fn get_options(&self) -> SanitizeOptions {
+        SanitizeOptions {
+            quiet: self._bquiet,
+            debug: self._bdebug,
+            profiling: self._bprofiling,
+            fallback: self._fallback.clone(),
+            charset: self._charset,
+            preserve_width: self._bpreserve_width,
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Compilation Methods
+     */
+
+    /// Freezes the current configuration into an immutable `CompiledSanitizer`.\
+    /// The requested `LanguageMap`s are merged in their priority order into one
+    /// single `LanguageMap`. So each character is looked up only once.\
+    /// Later changes to this `TextSanitizer` do not affect the `CompiledSanitizer`.
+    ///
+    /// # Example:
+    ///
+    /// Compile a `TextSanitizer` with the "de" and "en" Language Replacement Maps
+    /// ```
+    ///    use text_sanitizer::TextSanitizer;
+    ///
+    ///    let mut sanitizer = TextSanitizer::new();
+    ///
+    ///    sanitizer.add_request_language(&"de");
+    ///    sanitizer.add_request_language(&"en");
+    ///
+    ///    let compiled = sanitizer.compile();
+    ///
+    ///    assert_eq!(compiled.sanitize_string("Gr\u{fc}\u{df}e \u{2764}"), "Gruesse <3");
+    /// ```
+    pub fn compile(&self) -> CompiledSanitizer {
+        let mut lngrplmap = LanguageMap(HashMap::new());
+
+        if let Some(conv_map) = &self._oconv_map {
+            for slng in &self._vrqlangs {
+                if let Some(lngmap) = conv_map.0.get(slng.as_str()) {
+                    for (scode, srpl) in &lngmap.0 {
+                        //The first requested Language has the highest Priority
+                        if !lngrplmap.0.contains_key(scode) {
+                            lngrplmap.0.insert(scode.clone(), srpl.clone());
+                        }
+                    }
+                } //if let Some(lngmap) = conv_map.0.get(slng.as_str())
+            } //for slng in &self._vrqlangs
+        } //if let Some(conv_map) = &self._oconv_map
+
+        let mut conv_map = ConversionMap(HashMap::new());
+        let mut vrqlangs = vec![COMPILED_LANGUAGE.to_string()];
+
+        conv_map.0.insert(COMPILED_LANGUAGE.to_string(), lngrplmap);
+
+        if self._bpreserve_width {
+            //The Replacements of the same Width are looked up in all requested Languages
+            if let Some(src_map) = &self._oconv_map {
+                for slng in &self._vrqlangs {
+                    if let Some(lngmap) = src_map.0.get(slng.as_str()) {
+                        conv_map.0.insert(slng.clone(), lngmap.clone());
+                        vrqlangs.push(slng.clone());
+                    }
+                }
+            } //if let Some(src_map) = &self._oconv_map
+        } //if self._bpreserve_width
+
+        CompiledSanitizer {
+            _sanitizer: Arc::new(TextSanitizer {
+                _oconv_map: if self._oconv_map.is_some() {
+                    Some(conv_map)
+                } else {
+                    None
+                },
+                _vrqlangs: vrqlangs,
+                _bquiet: self._bquiet,
+                _bdebug: self._bdebug,
+                _bprofiling: self._bprofiling,
+                _fallback: self._fallback.clone(),
+                _charset: self._charset,
+                _vkeeprngs: self._vkeeprngs.clone(),
+                _bpreserve_width: self._bpreserve_width,
+                _odebug_sink: self._odebug_sink,
+            }),
+            _vrqlangs: Arc::new(self._vrqlangs.clone()),
+        }
+    }
+}
+
+//==============================================================================
+// Structure CompiledSanitizer Declaration
+
+/// Immutable version of a `TextSanitizer` created with `TextSanitizer::compile()`.\
+/// The priority order of the requested languages is already resolved into one
+/// single `LanguageMap`.\
+/// It is `Send + Sync` and can be shared between threads behind an `Arc`.
+/// Its clones share the merged `LanguageMap` and only copy a reference.
+///
+/// # Example:
+///
+/// Share one `CompiledSanitizer` between worker threads
+/// ```
+///    use std::sync::Arc;
+///    use std::thread;
+///
+///    use text_sanitizer::TextSanitizer;
+///
+///    let mut sanitizer = TextSanitizer::new();
+///
+///    sanitizer.add_request_language(&"es");
+///    sanitizer.add_request_language(&"en");
+///
+///    let compiled = Arc::new(sanitizer.compile());
+///
+///    let vworkers: Vec<_> = (0..4)
+///        .map(|_| {
+///            let worker_sanitizer = Arc::clone(&compiled);
+///
+///            thread::spawn(move || worker_sanitizer.sanitize_string("Acci\u{f3}n"))
+///        })
+///        .collect();
+///
+///    for worker in vworkers {
+///        assert_eq!(worker.join().unwrap(), "Accion");
+///    }
+/// ```
+
+#[derive(Clone, Debug)]
+pub struct CompiledSanitizer {
+    _sanitizer: Arc<TextSanitizer>,
+    _vrqlangs: Arc<Vec<String>>,
+}
+
+//==============================================================================
+// Structure CompiledSanitizer Implementation
+
+impl CompiledSanitizer {
+    /*----------------------------------------------------------------------------
+     * Sanitizing Methods
+     */
+
+    /// Parses the given reference to raw text data as array of bytes `u8` into
+    /// a new valid `std::str::String`.\
+    /// See `TextSanitizer::sanitize_u8()`
+    pub fn sanitize_u8(&self, text: &[u8]) -> String {
+        self._sanitizer.sanitize_u8(text)
+    }
+
+    /// Creates from a given string slice a simplified version with ASCII characters.\
+    /// See `TextSanitizer::sanitize_string()`
+    pub fn sanitize_string(&self, text: &str) -> String {
+        self._sanitizer.sanitize_u8(text.as_bytes())
+    }
+
+    /// Parses the given raw HTML data into a new valid `std::str::String`.\
+    /// See `TextSanitizer::sanitize_html_u8()`
+    pub fn sanitize_html_u8(&self, text: &[u8]) -> String {
+        self._sanitizer.sanitize_html_u8(text)
+    }
+
+    /// Creates from a given HTML string slice a simplified version with ASCII characters.\
+    /// See `TextSanitizer::sanitize_html_u8()`
+    pub fn sanitize_html_string(&self, text: &str) -> String {
+        self._sanitizer.sanitize_html_u8(text.as_bytes())
+    }
+
+    /// Sanitizes the text of a reader line by line.\
+    /// See `TextSanitizer::sanitize_lines()`
+    #[cfg(feature = "std")]
+    pub fn sanitize_lines<R: BufRead>(&self, reader: R) -> SanitizedLines<'_, R> {
+        self._sanitizer.sanitize_lines(reader)
+    }
+
+    /// Sanitizes the text of a reader line by line and passes each line to a callback.\
+    /// See `TextSanitizer::sanitize_lines_with()`
+    #[cfg(feature = "std")]
+    pub fn sanitize_lines_with<R, F>(&self, reader: R, callback: F) -> io::Result<usize>
+    where
+        R: BufRead,
+        F: FnMut(usize, &str, bool),
+    {
+        self._sanitizer.sanitize_lines_with(reader, callback)
+    }
+
+    /// Parses the given raw CSV or TSV data and sanitizes each field on its own.\
+    /// See `TextSanitizer::sanitize_csv_u8()`
+    pub fn sanitize_csv_u8(&self, text: &[u8], options: &CsvOptions) -> String {
+        self._sanitizer.sanitize_csv_u8(text, options)
+    }
+
+    /// Parses the given CSV or TSV string slice and sanitizes each field on its own.\
+    /// See `TextSanitizer::sanitize_csv_u8()`
+    pub fn sanitize_csv_string(&self, text: &str, options: &CsvOptions) -> String {
+        self._sanitizer.sanitize_csv_u8(text.as_bytes(), options)
+    }
+
+    /// Parses the given raw JSON data and sanitizes its string values.\
+    /// See `TextSanitizer::sanitize_json_u8()`
+    pub fn sanitize_json_u8(&self, text: &[u8], bkeys: bool) -> Result<String, JsonError> {
+        self._sanitizer.sanitize_json_u8(text, bkeys)
+    }
+
+    /// Parses the given JSON string slice and sanitizes its string values.\
+    /// See `TextSanitizer::sanitize_json_u8()`
+    pub fn sanitize_json_string(&self, text: &str, bkeys: bool) -> Result<String, JsonError> {
+        self._sanitizer.sanitize_json_u8(text.as_bytes(), bkeys)
+    }
+
+    /// Sanitizes large raw text data on all available cores.\
+    /// See `TextSanitizer::sanitize_u8_parallel()`
+    #[cfg(feature = "parallel")]
+    pub fn sanitize_u8_parallel(&self, text: &[u8]) -> String {
+        self._sanitizer.sanitize_u8_parallel(text)
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    pub fn is_quiet(&self) -> bool {
+        self._sanitizer.is_quiet()
+    }
+
+    pub fn is_debug(&self) -> bool {
+        self._sanitizer.is_debug()
+    }
+
+    pub fn is_profiling(&self) -> bool {
+        self._sanitizer.is_profiling()
+    }
+
+    /// Lists the requested languages in their priority order.
+    pub fn get_request_languages(&self) -> &[String] {
+        &self._vrqlangs
+    }
+
+    pub fn has_request_language(&self, slanguage: &str) -> bool {
+        self._vrqlangs.contains(&String::from(slanguage))
+    }
+}
+
+//==============================================================================
+// Structure TextSanitizerBuilder Declaration
+
+/// Builder to configure a `TextSanitizer` step by step.\
+/// It replaces the positional booleans of the different Constructors.
+/// By default it includes the built-in `LanguageMap`s "en", "de" and "es"
+/// and requests no language.
+///
+/// # Example:
+///
+/// Build a `TextSanitizer` with a custom `LanguageMap` and without the built-in maps
+/// ```
+///    use text_sanitizer::{Fallback, LanguageMap, TextSanitizer};
+///    use std::collections::HashMap;
+///
+///    let mut lang_map = LanguageMap(HashMap::new());
+///
+///    lang_map.0.insert("e4".to_string(), "a".to_string());
+///
+///    let sanitizer = TextSanitizer::builder()
+///        .builtin_maps(false)
+///        .language_map(&"fi", lang_map)
+///        .language(&"fi")
+///        .fallback(Fallback::Replace("?".to_string()))
+///        .quiet(true)
+///        .build();
+///
+///    assert_eq!(sanitizer.sanitize_string("Min\u{e4} \u{fc}"), "Mina ?");
+/// ```
+
+#[derive(Clone, Debug)]
+pub struct TextSanitizerBuilder {
+    _vlangmaps: Vec<(String, LanguageMap)>,
+    _vrqlangs: Vec<String>,
+    _bbuiltin: bool,
+    _options: SanitizeOptions,
+    _vkeeprngs: Vec<(char, char)>,
+    _odebug_sink: Option<&'static dyn DebugSink>,
+}
 
-        if !vunicode.is_empty() {
-            for sv in vunicode {
-                if sv.starts_with("(?") && sv.ends_with(')') {
-                    parse_result.push(sv[2..(sv.len() - 1)].to_owned());
-                } else {
-                    for c in sv.chars() {
-                        let suni = c.escape_unicode().to_string();
+//==============================================================================
+// Structure TextSanitizerBuilder Implementation
 
-                        parse_result.push(suni[3..(suni.len() - 1)].to_owned());
-                    } // for c in sv.chars()
-                } //if sv.starts_with("(?") && sv.ends_with(')')
-            } //for sv in vunicode
-        } //if ! vunicode.is_empty()
+impl Default for TextSanitizerBuilder {
+    /*----------------------------------------------------------------------------
+     * Default Constructor
+     */
 
-        parse_result
+    fn default() -> Self {
+        TextSanitizerBuilder::new()
     }
+}
 
-    /// Parses the given reference to raw text data as array of bytes `u8` into
-    /// a new valid `std::str::String`.
-    ///
-    /// # Parameters:
-    ///
-    /// * `text` - raw text data as array of bytes `u8`
-    ///
-    /// # Examples:
-    ///
-    /// Test data is the Sparkle Heart from the UTF-8 documentation examples but it is broken.\
-    /// According to the Official Standard Library Documentation at:\
-    /// [std::string::String::from_utf8()](https://doc.rust-lang.org/std/string/struct.String.html#method.from_utf8)\
-    /// this would produce a `FromUtf8Error` or **panic** the application
-    /// when used with `unwrap()`
-    /// ```
-    ///    //-------------------------------------
-    ///    // Test data is the Sparkle Heart from the UTF-8 documentation examples but it is broken
-    ///
-    ///    use text_sanitizer::TextSanitizer;
-    ///
-    ///    let vsparkle_heart = vec![240, 159, 119, 150];
-    ///
-    ///    let mut sanitizer = TextSanitizer::new_with_options(false, true, false);
-    ///
-    ///    sanitizer.add_request_language(&"en");
-    ///
-    ///    let srsout = sanitizer.sanitize_u8(&vsparkle_heart);
-    ///
-    ///    println!("sparkle_heart: '{}'", srsout);
-    ///
-    ///    assert_eq!(srsout, "(?f0)(?9f)w(?96)");
-    /// ```
-    pub fn sanitize_u8(&self, text: &[u8]) -> String {
-        let mut olngrplmap = None;
+impl TextSanitizerBuilder {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
 
-        if (self._bdebug && !self._bquiet) {
-            println!("vtext 0:'{:?}'", text);
+    pub fn new() -> TextSanitizerBuilder {
+        TextSanitizerBuilder {
+            _vlangmaps: Vec::new(),
+            _vrqlangs: Vec::new(),
+            _bbuiltin: true,
+            _options: SanitizeOptions::default(),
+            _vkeeprngs: Vec::new(),
+            _odebug_sink: None,
         }
+    }
 
-        if let Some(conv_map) = &self._oconv_map {
-            let mut srstxt = String::with_capacity(text.len());
-            let mut srptchrs = String::new();
-            let mut orpl = None;
-            let mut ic: usize = 0;
-            let mut icstrt: Option<usize> = None;
-            let mut icend: Option<usize> = None;
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
 
-            for uc in text {
-                if (self._bdebug && !self._bquiet) {
-                    srptchrs.push_str(&format!("; {} - {}:'{}'", ic, uc, char::from(*uc)));
-                }
+    /// Requests a language. The order in which the languages are requested
+    /// is the order in which their `LanguageMap`s are applied.
+    pub fn language(mut self, language: &str) -> Self {
+        let slang = String::from(language);
 
-                if (*uc >= 32 as u8 && *uc < 127 as u8) || (*uc == 10 as u8) || (*uc == 9 as u8) {
-                    //------------------------
-                    //Valid ASCII Character
+        if !self._vrqlangs.contains(&slang) {
+            self._vrqlangs.push(slang);
+        }
 
-                    //srptchrs.push_str(" - ascii");
+        self
+    }
 
-                    if icstrt.is_some() {
-                        //------------------------
-                        //Pending Non ASCII Characters
+    /// Requests several languages in their priority order.
+    pub fn languages<I, S>(mut self, languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for slang in languages {
+            self = self.language(slang.as_ref());
+        }
 
-                        icend = Some(ic);
+        self
+    }
 
-                        if (self._bdebug && !self._bquiet) {
-                            println!(
-                                "pdg spec chars '{} - {}': '{:?}'",
-                                icstrt.unwrap(),
-                                icend.unwrap(),
-                                &text[icstrt.unwrap()..icend.unwrap()]
-                            );
-                        }
+    /// Adds all `LanguageMap`s of a custom `ConversionMap`.\
+    /// They replace built-in `LanguageMap`s with the same language shortcode.
+    pub fn conversion_map(mut self, conversion_map: ConversionMap) -> Self {
+        for (slang, lngmap) in conversion_map.0 {
+            self = self.language_map(&slang, lngmap);
+        }
 
-                        //Parse the slice of Non ASCII Characters
-                        let vuni = self.parse_unicode(&text[icstrt.unwrap()..icend.unwrap()]);
+        self
+    }
 
-                        if (self._bdebug && !self._bquiet) {
-                            print!("= {:?}", vuni);
-                        }
+    /// Adds or replaces a custom `LanguageMap`.
+    pub fn language_map(mut self, language: &str, language_map: LanguageMap) -> Self {
+        self._vlangmaps.push((language.to_string(), language_map));
 
-                        for suni in vuni {
-                            orpl = None;
-
-                            for slng in &self._vrqlangs {
-                                if orpl.is_none() {
-                                    olngrplmap = conv_map.0.get(slng.as_str());
-
-                                    if let Some(lngmap) = olngrplmap {
-                                        orpl = lngmap.0.get(suni.as_str());
-                                    }
-                                } //if orpl.is_none()
-                            } //for slng in vrqlanguages
-
-                            match orpl {
-                                Some(rpl) => {
-                                    srstxt.push_str(rpl);
-
-                                    if (self._bdebug && !self._bquiet) {
-                                        print!(" -> '{}'", rpl);
-                                    }
-                                }
-                                None => {
-                                    srstxt.push_str(&format!("(?{})", &suni));
-
-                                    if (self._bdebug && !self._bquiet) {
-                                        print!(" -> '(?{})'", &suni);
-                                    }
-                                } //Some(rpl)
-                            } //match orpl
-                        } //for suni in vuni
+        self
+    }
 
-                        if (self._bdebug && !self._bquiet) {
-                            println!("'");
-                        } //if(bdbg && ! bqt)
-                          /**/
+    /// Sets whether the built-in `LanguageMap`s "en", "de" and "es" are included.
+    pub fn builtin_maps(mut self, bbuiltin: bool) -> Self {
+        self._bbuiltin = bbuiltin;
 
-                        icstrt = None;
-                    } //if icstrt.is_some()
+        self
+    }
 
-                    //Add the valid ASCII Character
-                    srstxt.push(char::from(*uc));
-                } else {
-                    //------------------------
-                    //Non ASCII Character
+    /// Sets how characters without any mapping are handled.
+    pub fn fallback(mut self, fallback: Fallback) -> Self {
+        self._options.fallback = fallback;
 
-                    srptchrs.push_str(&format!(" - non-ascii '{:?}", icstrt));
+        self
+    }
 
-                    if icstrt.is_none() {
-                        icstrt = Some(ic);
-                        //if(bdbg && ! bqt) {
-                        srptchrs.push_str(&format!(" > {:?} - {:?}'|", icstrt, icend));
-                        //}
-                    } else {
-                        srptchrs.push_str(&format!(" - {:?}'|", icend));
-                    } //if (uc[0] >= 32 as u8 && uc[0] < 127 as u8)
-                } //if (*uc >= 32 as u8 && *uc < 127 as u8) || ( *uc == 10 as u8 )
-
-                ic += 1;
-            } //for uc in text
-
-            if icstrt.is_some() {
-                icend = Some(ic);
-
-                if (self._bdebug && !self._bquiet) {
-                    print!(
-                        "\nrst spec char '{} - {}': '{:?}",
-                        icstrt.unwrap(),
-                        icend.unwrap(),
-                        &text[icstrt.unwrap()..icend.unwrap()]
-                    );
-                }
+    /// Adds a single character to the allowlist.
+    pub fn keep_character(self, c: char) -> Self {
+        self.keep_range(c, c)
+    }
 
-                let vuni = self.parse_unicode(&text[icstrt.unwrap()..icend.unwrap()]);
+    /// Adds a range of characters to the allowlist.
+    pub fn keep_range(mut self, first: char, last: char) -> Self {
+        self._vkeeprngs.push((first, last));
 
-                if (self._bdebug && !self._bquiet) {
-                    print!(" | {:?}", vuni);
-                }
+        self
+    }
 
-                for suni in vuni {
-                    orpl = None;
+    /// Keeps the display width of each line like in the source.
+    pub fn preserve_width(mut self, bpreserve: bool) -> Self {
+        self._options.preserve_width = bpreserve;
 
-                    for slng in &self._vrqlangs {
-                        if orpl.is_none() {
-                            olngrplmap = conv_map.0.get(slng.as_str());
+        self
+    }
 
-                            if let Some(lngmap) = olngrplmap {
-                                orpl = lngmap.0.get(suni.as_str());
-                            }
-                        } //if orpl.is_none()
-                    } //for slng in vrqlanguages
+    /// Sets the character set of the sanitized output.
+    pub fn target_charset(mut self, charset: TargetCharset) -> Self {
+        self._options.charset = charset;
 
-                    match orpl {
-                        Some(rpl) => {
-                            srstxt.push_str(rpl);
+        self
+    }
 
-                            if (self._bdebug && !self._bquiet) {
-                                print!(" -> '{}'", rpl);
-                            }
-                        }
-                        None => {
-                            srstxt.push_str(&format!("(?{})", &suni));
+    pub fn quiet(mut self, bquiet: bool) -> Self {
+        self._options.quiet = bquiet;
 
-                            if (self._bdebug && !self._bquiet) {
-                                print!(" -> '(?{})'", &suni);
-                            }
-                        } //Some(rpl)
-                    } //match orpl
-                } //for suni in vuni
+        self
+    }
 
-                if (self._bdebug && !self._bquiet) {
-                    print!("'");
-                } //if(bdbg && ! bqt)
+    pub fn debug(mut self, bdebug: bool) -> Self {
+        self._options.debug = bdebug;
 
-                icstrt = None;
-            } //if icstrt.is_some()
+        self
+    }
 
-            if (self._bdebug && !self._bquiet) {
-                srptchrs.push_str(&format!("; chr cnt '{}'", ic));
+    pub fn profiling(mut self, bprofiling: bool) -> Self {
+        self._options.profiling = bprofiling;
+
+        self
+    }
 
-                println!("; sanitze done.");
-                println!("chrs rpt: '{:?}'", &srptchrs);
+    /// Sets the receiver of the debug messages.
+    pub fn debug_sink(mut self, sink: &'static dyn DebugSink) -> Self {
+        self._odebug_sink = Some(sink);
 
-                let vsttrpt: Vec<char> = String::from_utf8_lossy(text).to_mut().chars().collect();
+        self
+    }
 
-                println!("stt rpt chrs (count : '{}'):\n{:?}", vsttrpt.len(), vsttrpt);
+    /// Sets all runtime options at once.
+    pub fn options(mut self, options: &SanitizeOptions) -> Self {
+        self._options = options.clone();
 
-                println!("stt chrs ascii:");
+        self
+    }
 
-                for c in &vsttrpt {
-                    if !c.is_ascii() {
-                        print!("{}|", c.escape_unicode().to_string());
-                    } else {
-                        print!("{}|", c);
-                    }
-                } //for c in &vsttrpt
+    /*----------------------------------------------------------------------------
+     * Build Methods
+     */
 
-                println!();
-            } //if(bdbg && ! bqt)
+    /// Creates the configured `TextSanitizer`.
+    pub fn build(self) -> TextSanitizer {
+        let mut sanitizer = TextSanitizer::new_with_sanitize_options(&self._options);
 
-            //Return the sanitized String
-            srstxt
-        } else {
-            String::from_utf8_lossy(text).into_owned()
+        if !self._bbuiltin {
+            sanitizer.set_conversion_map(ConversionMap(HashMap::new()));
         }
-    }
 
-    /// Creates from a given string slice a simplified version with ASCII characters.
-    ///
-    /// # Parameters:
-    ///
-    /// * `text` - String slice of text to sanitize
-    ///
-    /// # Examples:
-    ///
-    /// ```
-    ///    //-------------------------------------
-    ///    // Test data is the Sparkle Heart from the UTF-8 documentation examples.
-    ///    // It will be sanitized into the ASCII Characters " <3 "
-    ///
-    ///    use text_sanitizer::TextSanitizer;
-    ///
-    ///    let vsparkle_heart = vec![240, 159, 146, 150];
-    ///
-    ///    let mut sanitizer = TextSanitizer::new();
-    ///
-    ///    sanitizer.add_request_language(&"en");
-    ///
-    ///    let srsout = match std::str::from_utf8(&vsparkle_heart) {
-    ///         Ok(s) => sanitizer.sanitize_string(s),
-    ///         Err(_) => sanitizer.sanitize_u8(&vsparkle_heart),
-    ///    };
-    ///
-    ///    println!("sparkle_heart: '{}'", srsout);
-    ///
-    ///    assert_eq!(srsout, "<3");
-    /// ```
-    pub fn sanitize_string(&self, text: &str) -> String {
-        self.sanitize_u8(text.as_bytes())
-    }
+        if let Some(sink) = self._odebug_sink {
+            sanitizer.set_debug_sink(sink);
+        }
 
-    /*----------------------------------------------------------------------------
-     * Consultation Methods
-     */
+        for (slang, lngmap) in self._vlangmaps {
+            sanitizer.set_language_map(&slang, lngmap);
+        }
 
-    pub fn is_quiet(&self) -> bool {
-        self._bquiet
-    }
+        for slang in &self._vrqlangs {
+            sanitizer.add_request_language(slang);
+        }
 
-    pub fn is_debug(&self) -> bool {
-        self._bdebug
-    }
+        for (first, last) in self._vkeeprngs {
+            sanitizer.add_keep_range(first, last);
+        }
 
-    pub fn is_profiling(&self) -> bool {
-        self._bprofiling
+        sanitizer
     }
 
-    pub fn has_request_language(&self, slanguage: &str) -> bool {
-        self._vrqlangs.contains(&String::from(slanguage))
+    /// Creates the configured `TextSanitizer` and freezes it
+    /// into a `CompiledSanitizer`.
+    pub fn compile(self) -> CompiledSanitizer {
+        self.build().compile()
     }
 }
 
@@ -1054,6 +2540,67 @@ pub fn sanitize_string(text: &str, vrqlanguages: &Vec<String>, options: &str) ->
     sanitizer.sanitize_u8(text.as_bytes())
 }
 
+/// Parses the given reference to raw text data as array of bytes `u8` into
+/// a new valid `std::str::String` with typed `SanitizeOptions`.
+///
+/// # Parameters:
+///
+/// * `text` - raw text data as array of bytes `u8`
+/// * `vrqlanguages` - Slice of language references. Currently only 'en', 'es' and 'de'
+///   are recognized.
+/// * `options` - typed runtime options
+///
+/// # Examples:
+///
+/// ```
+///    use text_sanitizer::sanitizer::sanitize_u8_with_options;
+///    use text_sanitizer::{Fallback, SanitizeOptions};
+///
+///    let vsparkle_heart = vec![240, 159, 119, 150];
+///
+///    let vrqlngs: Vec<String> = vec![String::from("en")];
+///
+///    let options = SanitizeOptions {
+///        quiet: true,
+///        fallback: Fallback::Remove,
+///        ..SanitizeOptions::default()
+///    };
+///
+///    let srsout = sanitize_u8_with_options(&vsparkle_heart, &vrqlngs, &options);
+///
+///    assert_eq!(srsout, "w");
+/// ```
+pub fn sanitize_u8_with_options(
+    text: &[u8],
+    vrqlanguages: &[String],
+    options: &SanitizeOptions,
+) -> String {
+    let mut sanitizer = TextSanitizer::new_with_sanitize_options(options);
+
+    for srqlang in vrqlanguages {
+        sanitizer.add_request_language(srqlang.as_str());
+    }
+
+    sanitizer.sanitize_u8(text)
+}
+
+/// Creates from a given string slice a simplified version with ASCII characters
+/// with typed `SanitizeOptions`.
+///
+/// # Parameters:
+///
+/// * `text` - String of text to sanitize
+/// * `vrqlanguages` - Slice of language references. Currently only 'en', 'es' and 'de'
+///   are recognized.
+/// * `options` - typed runtime options
+pub fn sanitize_string_with_options(
+    text: &str,
+    vrqlanguages: &[String],
+    options: &SanitizeOptions,
+) -> String {
+    sanitize_u8_with_options(text.as_bytes(), vrqlanguages, options)
+}
+
 //==============================================================================
 // Unit Tests
 
@@ -1142,7 +2689,6 @@ fn sanitizer_new_conversion_map() {
     let finnish_res = "Mina tahdon kernaasti puhua suomen kielen, [mutta] en mina taida.";
     let german_res = "Deutsch verwendet ahnliche Umlaute, wird aber anders (?fc)bersetzt.";
 
-
     let mut conv_map = ConversionMap(HashMap::with_capacity(2));
     let mut lang_map = LanguageMap(HashMap::with_capacity(1));
 
@@ -1327,3 +2873,238 @@ fn hearts_game() {
 
     assert_eq!(srsout, "<3<3w(?fa)(?f8)<3(?f7)(?be)");
 }
+
+#[test]
+fn compiled_language_priority() {
+    let finnish_data = "Min\u{e4} tahdon kernaasti puhua suomen kielen. Gr\u{fc}\u{df}e \u{2764}";
+    let finnish_res = "Mina tahdon kernaasti puhua suomen kielen. Gruesse <3";
+    let german_res = "Minae tahdon kernaasti puhua suomen kielen. Gruesse <3";
+
+    let mut sanitizer = TextSanitizer::new();
+
+    let mut lang_map = LanguageMap(HashMap::with_capacity(1));
+
+    lang_map.0.insert("e4".to_string(), "a".to_string());
+
+    sanitizer.set_language_map("fi", lang_map);
+
+    sanitizer.add_request_language("fi");
+    sanitizer.add_request_language("de");
+    sanitizer.add_request_language("en");
+
+    let compiled = sanitizer.compile();
+
+    sanitizer.clear_request_languages();
+
+    sanitizer.add_request_language("de");
+    sanitizer.add_request_language("fi");
+    sanitizer.add_request_language("en");
+
+    assert_eq!(compiled.sanitize_string(finnish_data), finnish_res);
+    assert_eq!(sanitizer.sanitize_string(finnish_data), german_res);
+    assert_eq!(
+        sanitizer.compile().sanitize_string(finnish_data),
+        german_res
+    );
+    assert_eq!(compiled.get_request_languages(), ["fi", "de", "en"]);
+}
+
+#[test]
+fn compiled_send_sync() {
+    fn assert_send_sync<T: Send + Sync + Clone>() {}
+
+    assert_send_sync::<CompiledSanitizer>();
+
+    let compiled = TextSanitizer::new().compile();
+    let compiled_clone = compiled.clone();
+
+    assert!(Arc::ptr_eq(
+        &compiled._sanitizer,
+        &compiled_clone._sanitizer
+    ));
+    assert!(Arc::ptr_eq(&compiled._vrqlangs, &compiled_clone._vrqlangs));
+}
+
+#[test]
+fn builder_options() {
+    let spanish_data = "Acci\u{f3}n fallida \u{2764} Gr\u{fc}\u{df}e";
+
+    let sanitizer = TextSanitizer::builder().debug(true).build();
+
+    assert!(sanitizer.is_debug());
+    assert!(!sanitizer.is_quiet());
+
+    let sanitizer = TextSanitizer::builder()
+        .languages(vec!["es", "en"])
+        .fallback(Fallback::Remove)
+        .build();
+
+    assert!(!sanitizer.is_debug());
+    assert_eq!(
+        sanitizer.sanitize_string(spanish_data),
+        "Accion fallida <3 Gre"
+    );
+
+    let sanitizer = TextSanitizer::builder()
+        .builtin_maps(false)
+        .language("es")
+        .build();
+
+    assert_eq!(
+        sanitizer.sanitize_string(spanish_data),
+        "Acci(?f3)n fallida (?2764) Gr(?fc)(?df)e"
+    );
+
+    let compiled = TextSanitizer::builder()
+        .languages(vec![String::from("de"), String::from("es")])
+        .fallback(Fallback::Replace("?".to_string()))
+        .compile();
+
+    assert_eq!(
+        compiled.sanitize_string(spanish_data),
+        "Accion fallida ? Gruesse"
+    );
+}
+
+#[test]
+fn proc_sanitize_options() {
+    let options = SanitizeOptions::from_string("-q --debug -v");
+
+    assert_eq!(
+        options,
+        SanitizeOptions {
+            quiet: true,
+            debug: true,
+            ..SanitizeOptions::default()
+        }
+    );
+
+    let vrqlngs: Vec<String> = vec![String::from("de")];
+
+    let srsout = sanitize_string_with_options("Gr\u{fc}\u{df}e \u{2764}", &vrqlngs, &options);
+
+    assert_eq!(srsout, "Gruesse (?2764)");
+}
+
+#[test]
+fn target_charset_latin1() {
+    let german_data = "Gr\u{fc}\u{df}e aus M\u{fc}nchen \u{2764}\r\n5\u{20ac}";
+
+    let sanitizer = TextSanitizer::builder()
+        .languages(vec!["de", "en"])
+        .target_charset(TargetCharset::Latin1)
+        .build();
+
+    assert_eq!(sanitizer.get_target_charset(), TargetCharset::Latin1);
+    assert_eq!(
+        sanitizer.sanitize_string(german_data),
+        "Gr\u{fc}\u{df}e aus M\u{fc}nchen <3\n5EUR"
+    );
+    //Broken Bytes are still replaced
+    assert_eq!(sanitizer.sanitize_u8(b"M\xfcnchen \xfa"), "Muenchen (?fa)");
+    assert_eq!(
+        sanitizer.compile().sanitize_string(german_data),
+        "Gr\u{fc}\u{df}e aus M\u{fc}nchen <3\n5EUR"
+    );
+}
+
+#[test]
+fn target_charset_printable_utf8() {
+    let mixed_data = "Acci\u{f3}n 5\u{20ac} \u{3b1}\u{3b2} \u{1f496}\u{fe0f} \u{251c}\u{2500}";
+
+    let sanitizer = TextSanitizer::builder()
+        .languages(vec!["es", "en"])
+        .target_charset(TargetCharset::PrintableUtf8)
+        .build();
+
+    assert_eq!(
+        sanitizer.sanitize_string(mixed_data),
+        "Acci\u{f3}n 5\u{20ac} \u{3b1}\u{3b2} <3(?fe0f) |--"
+    );
+    assert_eq!(
+        sanitizer.sanitize_u8(&[240, 159, 146, 150, 119, 250]),
+        "<3w(?fa)"
+    );
+}
+
+#[test]
+fn keep_characters() {
+    let report_data = "Temp: 21\u{b0}C \u{2764} Preis: 5\u{20ac}\r\n";
+
+    let mut sanitizer = TextSanitizer::builder()
+        .language("en")
+        .keep_character('\u{20ac}')
+        .keep_range('\u{b0}', '\u{b0}')
+        .build();
+
+    assert!(sanitizer.is_keep_character('\u{b0}'));
+    assert!(!sanitizer.is_keep_character('\u{b1}'));
+    assert_eq!(
+        sanitizer.sanitize_string(report_data),
+        "Temp: 21\u{b0}C <3 Preis: 5\u{20ac}\n"
+    );
+    assert_eq!(
+        sanitizer.compile().sanitize_string(report_data),
+        "Temp: 21\u{b0}C <3 Preis: 5\u{20ac}\n"
+    );
+    //Broken Bytes are never kept
+    assert_eq!(sanitizer.sanitize_u8(b"21\xb0C"), "21(?b0)C");
+
+    sanitizer.clear_keep_characters();
+
+    assert_eq!(
+        sanitizer.sanitize_string(report_data),
+        "Temp: 21(?b0)C <3 Preis: 5EUR\n"
+    );
+}
+
+#[test]
+fn preserve_width_table() {
+    let table_data = "ID | Aktion   | Gr\u{f6}\u{df}e\n 1 | L\u{f6}schen | 12\n 2 | \u{251c}\u{2500} Neu   | 3\n";
+
+    let mut sanitizer = TextSanitizer::builder()
+        .languages(vec!["de", "en"])
+        .preserve_width(true)
+        .build();
+
+    assert_eq!(
+        sanitizer.sanitize_string(table_data),
+        "ID | Aktion   | Groesse\n 1 | Loeschen | 12\n 2 | |-- Neu  | 3\n"
+    );
+    assert_eq!(
+        sanitizer.compile().sanitize_string(table_data),
+        "ID | Aktion   | Groesse\n 1 | Loeschen | 12\n 2 | |-- Neu  | 3\n"
+    );
+
+    //A same-width Replacement of a later Language is preferred
+    let mut lang_map = LanguageMap(HashMap::new());
+
+    lang_map.0.insert("f6".to_string(), "o".to_string());
+    sanitizer.set_language_map("fi", lang_map);
+    sanitizer.add_request_language("fi");
+
+    assert_eq!(sanitizer.sanitize_string("L\u{f6}schen | 1"), "Loschen | 1");
+
+    //Removed Characters are padded
+    sanitizer.set_fallback(Fallback::Remove);
+
+    assert_eq!(sanitizer.sanitize_string("a\u{3b1}b c|\n"), "ab  c|\n");
+    assert_eq!(sanitizer.sanitize_string("a\u{3b1}b"), "ab ");
+
+    //The Values of structured Formats are not padded
+    assert_eq!(
+        sanitizer.sanitize_json_string("[\"a\u{3b1}b\"]", false),
+        Ok(String::from("[\"ab\"]"))
+    );
+    assert_eq!(
+        sanitizer.sanitize_html_string("<i>a\u{3b1}b</i>"),
+        "<i>ab</i>"
+    );
+
+    sanitizer.set_preserve_width(false);
+
+    assert_eq!(
+        sanitizer.sanitize_string("L\u{f6}schen | 1"),
+        "Loeschen | 1"
+    );
+}
@@ -0,0 +1,76 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage sink.rs
+
+* This module implements the output of the debug messages
+*
+*---------------------------------
+* Requirements:
+* - The Standard Library is only needed for the "StdoutSink"
+*/
+
+use core::fmt;
+
+//==============================================================================
+// Trait DebugSink Declaration
+
+/// Receiver of the detailed activity messages of a `TextSanitizer` in debug mode.\
+/// Without the cargo feature `std` there is no standard output. So the debug messages
+/// are dropped unless a `DebugSink` is set.
+///
+/// # Example:
+///
+/// Count the debug messages instead of printing them
+/// ```
+///    use std::fmt;
+///    use std::sync::atomic::{AtomicUsize, Ordering};
+///
+///    use text_sanitizer::{DebugSink, TextSanitizer};
+///
+///    struct CountingSink(AtomicUsize);
+///
+///    impl DebugSink for CountingSink {
+///        fn write_debug(&self, _message: fmt::Arguments) {
+///            self.0.fetch_add(1, Ordering::Relaxed);
+///        }
+///    }
+///
+///    static COUNTING_SINK: CountingSink = CountingSink(AtomicUsize::new(0));
+///
+///    let mut sanitizer = TextSanitizer::new_with_options(false, true, false);
+///
+///    sanitizer.set_debug_sink(&COUNTING_SINK);
+///    sanitizer.add_request_language(&"en");
+///
+///    assert_eq!(sanitizer.sanitize_string("\u{2764}"), "<3");
+///    assert!(COUNTING_SINK.0.load(Ordering::Relaxed) > 0);
+/// ```
+pub trait DebugSink: Sync {
+    /// Receives one piece of a debug message.
+    /// Line breaks are part of the message.
+    fn write_debug(&self, message: fmt::Arguments);
+}
+
+impl fmt::Debug for dyn DebugSink {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("DebugSink")
+    }
+}
+
+//==============================================================================
+// Structure StdoutSink Declaration
+
+/// `DebugSink` that prints the debug messages to STDOUT.\
+/// This is the default behaviour when the cargo feature `std` is enabled.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct StdoutSink;
+
+#[cfg(feature = "std")]
+impl DebugSink for StdoutSink {
+    fn write_debug(&self, message: fmt::Arguments) {
+        print!("{}", message);
+    }
+}
@@ -0,0 +1,56 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer
+* @subpackage width.rs
+
+* This module implements the display width of characters on a terminal
+*
+*---------------------------------
+* Requirements:
+* - no further Rust crates
+*/
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Estimates the number of terminal columns a character occupies.\
+/// Control characters, combining marks and zero width characters take no column.
+/// East Asian wide characters and emoji take 2 columns. All others take 1 column.
+pub fn char_width(c: char) -> usize {
+    if c.is_control() {
+        return 0;
+    }
+
+    match c as u32 {
+        //Combining Marks and Zero Width Characters
+        0x0300..=0x036f | 0x200b..=0x200f | 0x2060..=0x2064 | 0xfe00..=0xfe0f | 0xfeff => 0,
+        //East Asian Wide and Fullwidth Characters
+        0x1100..=0x115f | 0x2e80..=0x303e | 0x3041..=0x33ff | 0x3400..=0x4dbf => 2,
+        0x4e00..=0x9fff | 0xa000..=0xa4cf | 0xac00..=0xd7a3 | 0xf900..=0xfaff => 2,
+        0xfe30..=0xfe4f | 0xff00..=0xff60 | 0xffe0..=0xffe6 => 2,
+        //Emoji and Pictographs
+        0x1f300..=0x1f64f | 0x1f900..=0x1f9ff | 0x20000..=0x3fffd => 2,
+        _ => 1,
+    }
+}
+
+/// Estimates the number of terminal columns a string occupies.
+pub fn str_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn display_widths() {
+    assert_eq!(char_width('a'), 1);
+    assert_eq!(char_width('\r'), 0);
+    assert_eq!(char_width('\u{fc}'), 1);
+    assert_eq!(char_width('\u{301}'), 0);
+    assert_eq!(char_width('\u{251c}'), 1);
+    assert_eq!(char_width('\u{4e2d}'), 2);
+    assert_eq!(char_width('\u{1f496}'), 2);
+    assert_eq!(str_width("Gr\u{fc}\u{df}e <3"), 8);
+}
@@ -22,7 +22,7 @@ mod file_tests {
 
     extern crate text_sanitizer;
 
-    use text_sanitizer::TextSanitizer;
+    use text_sanitizer::{Fallback, TextSanitizer};
 
     // The derive implements <RuntimeOptions> == <RuntimeOptions> comparisons
     #[derive(PartialEq)]
@@ -281,4 +281,90 @@ mod file_tests {
             assert!(test_file(&file, &[RuntimeOptions::Debug]).is_ok());
         }
     }
+
+    #[test]
+    fn preserve_width_file() {
+        let maindir = find_maindir(&[]).expect("maindir not found");
+        let datadir = maindir.join("tests/data");
+
+        let vtstdta = read_file(&datadir.join("yum-history_list.txt")).expect("file not readable");
+        let vrsdta =
+            read_file(&datadir.join("yum-history_list_result.txt")).expect("file not readable");
+        let stst = String::from_utf8(vtstdta).expect("test file not UTF-8");
+
+        let mut sanitizer = TextSanitizer::new();
+
+        sanitizer.add_request_language("es");
+        sanitizer.add_request_language("de");
+        sanitizer.add_request_language("en");
+        sanitizer.set_preserve_width(true);
+
+        let srsout = sanitizer.sanitize_string(&stst);
+
+        println!("yum history san dmp:\n'{}'", srsout);
+
+        assert_eq!(srsout, String::from_utf8_lossy(&vrsdta));
+
+        //Removed Characters are padded so the Columns stay aligned
+        sanitizer.clear_request_languages();
+        sanitizer.set_fallback(Fallback::Remove);
+
+        let srsout = sanitizer.sanitize_string(&stst);
+
+        println!("yum history san dmp:\n'{}'", srsout);
+
+        assert!(srsout.starts_with("ID     | Lnea  de comandos        | Da  y hora       |"));
+
+        for (ssrcline, srsline) in stst.lines().zip(srsout.lines()) {
+            let vsrcpipes: Vec<usize> = ssrcline
+                .chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '|')
+                .map(|(ic, _)| ic)
+                .collect();
+            let vrspipes: Vec<usize> = srsline.match_indices('|').map(|(ic, _)| ic).collect();
+
+            assert_eq!(srsline.len(), ssrcline.chars().count());
+            assert_eq!(vrspipes, vsrcpipes);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_files() {
+        let mut lstdatafiles: Vec<PathBuf> = Vec::new();
+        let mut vtstdta: Vec<u8> = Vec::new();
+
+        let maindir = find_maindir(&[]).expect("maindir not found");
+        let mut datadir = maindir.clone();
+
+        datadir.push("tests/data");
+
+        assert!(list_testdata(&datadir, &mut lstdatafiles, &[]).is_ok());
+
+        //Build a Document of several MB which splits into many Chunks
+        for _ in 0..50 {
+            for file in &lstdatafiles {
+                vtstdta.append(&mut read_file(file).expect("test file not readable"));
+            }
+        }
+
+        let mut sanitizer = TextSanitizer::new();
+
+        sanitizer.add_request_language("en");
+        sanitizer.add_request_language("es");
+        sanitizer.add_request_language("de");
+
+        let srsout = sanitizer.sanitize_u8(&vtstdta);
+        let srsparallel = sanitizer.sanitize_u8_parallel(&vtstdta);
+
+        println!(
+            "dta cnt: '{}'; san cnt: '{}'; par cnt: '{}'",
+            vtstdta.len(),
+            srsout.len(),
+            srsparallel.len()
+        );
+
+        assert_eq!(srsparallel, srsout);
+    }
 }
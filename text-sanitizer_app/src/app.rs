@@ -10,13 +10,27 @@
 *---------------------------------
 * Requirements:
 * - The Rust crate "text-sanitizer" must be installed
+* - The Rust crate "serde_json" must be installed
 */
 
 extern crate text_sanitizer;
 
-use text_sanitizer::TextSanitizer;
+use text_sanitizer::{ConversionMap, CsvOptions, Fallback, TextSanitizer};
 
+use crate::command::{run_command, CommandOptions};
+use crate::config::{parse_fallback, Config};
+use crate::diff::build_diff_report;
+use crate::files::{build_backup_path, write_atomic};
+#[cfg(target_os = "linux")]
+use crate::pty::run_pty;
+use crate::serve::{run_service, SanitizerService};
+use crate::stream::{sanitize_stream, StreamSanitizer};
+use crate::strict::{check_strict, StrictViolation};
+
+use std::fs;
 use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /*
 extern crate serde; // 1.0.149;
@@ -103,6 +117,27 @@ fn main() {
 
 */
 
+//==============================================================================
+// Constants
+
+/// Exit Code of a successful run
+pub const EXIT_OK: i32 = 0;
+/// Exit Code when reading or writing the data failed
+pub const EXIT_IO_ERROR: i32 = 1;
+/// Exit Code of invalid parameters
+pub const EXIT_USAGE_ERROR: i32 = 2;
+/// Exit Code when a character stays unmapped in the strict mode
+pub const EXIT_UNMAPPED: i32 = 3;
+/// Exit Code when an invalid byte stays unmapped in the strict mode
+pub const EXIT_INVALID_ENCODING: i32 = 4;
+/// Exit Code when the data is not valid in the selected format like JSON
+pub const EXIT_INVALID_FORMAT: i32 = 5;
+/// Exit Code when the wrapped command could not be started
+pub const EXIT_COMMAND_NOT_RUN: i32 = 127;
+
+/// Time after which an incomplete line is written in the streaming mode
+const FLUSH_TIMEOUT_MS: u64 = 200;
+
 //==============================================================================
 // Structure RunTextSanitizer Declaration
 
@@ -112,10 +147,28 @@ pub struct RunTextSanitizer {
     _vinput: Vec<u8>,
     _srsout: String,
     _vrqlangs: Vec<String>,
+    _bauto_lang: bool,
     _bimport: bool,
     _bquiet: bool,
     _bdebug: bool,
     _bprofiling: bool,
+    _bhtml: bool,
+    _bjson: bool,
+    _bjson_keys: bool,
+    _ocsv: Option<CsvOptions>,
+    _bdiff: bool,
+    _bstrict: bool,
+    _bbatch: bool,
+    _omax_input: Option<u64>,
+    _vcommand: Vec<String>,
+    _ocommand: CommandOptions,
+    _bpty: bool,
+    _bstrip_escapes: bool,
+    _vfiles: Vec<PathBuf>,
+    _ooutput: Option<PathBuf>,
+    _oinplace: Option<String>,
+    _olisten: Option<String>,
+    _osocket: Option<PathBuf>,
     _ierr: i32,
 }
 
@@ -144,11 +197,29 @@ impl RunTextSanitizer {
             _vinput: Vec::new(),
             _srsout: String::new(),
             _vrqlangs: Vec::new(),
+            _bauto_lang: false,
             _bimport: false,
             _bquiet: false,
             _bdebug: false,
             _bprofiling: false,
-            _ierr: 0,
+            _bhtml: false,
+            _bjson: false,
+            _bjson_keys: false,
+            _ocsv: None,
+            _bdiff: false,
+            _bstrict: false,
+            _bbatch: false,
+            _omax_input: None,
+            _vcommand: Vec::new(),
+            _ocommand: CommandOptions::default(),
+            _bpty: false,
+            _bstrip_escapes: false,
+            _vfiles: Vec::new(),
+            _ooutput: None,
+            _oinplace: None,
+            _olisten: None,
+            _osocket: None,
+            _ierr: EXIT_OK,
         };
 
         app.init();
@@ -168,11 +239,29 @@ impl RunTextSanitizer {
             _vinput: Vec::new(),
             _srsout: String::new(),
             _vrqlangs: Vec::new(),
+            _bauto_lang: false,
             _bimport: bimport,
             _bquiet: bquiet,
             _bdebug: bdebug,
             _bprofiling: bprofiling,
-            _ierr: 0,
+            _bhtml: false,
+            _bjson: false,
+            _bjson_keys: false,
+            _ocsv: None,
+            _bdiff: false,
+            _bstrict: false,
+            _bbatch: false,
+            _omax_input: None,
+            _vcommand: Vec::new(),
+            _ocommand: CommandOptions::default(),
+            _bpty: false,
+            _bstrip_escapes: false,
+            _vfiles: Vec::new(),
+            _ooutput: None,
+            _oinplace: None,
+            _olisten: None,
+            _osocket: None,
+            _ierr: EXIT_OK,
         };
 
         app.init();
@@ -207,7 +296,193 @@ impl RunTextSanitizer {
         self._sanitizer.set_profiling(bprofiling);
     }
 
-    pub fn add_request_language(&mut self, slanguage: &str) {
+    /// Keeps the display width of each line. STDIN is then read in the batch mode.
+    pub fn set_preserve_width(&mut self, bpreserve: bool) {
+        self._sanitizer.set_preserve_width(bpreserve);
+    }
+
+    /// Enables the HTML-aware mode which decodes character references like "&auml;"
+    /// and keeps the markup intact.
+    pub fn set_html(&mut self, bhtml: bool) {
+        self._bhtml = bhtml;
+    }
+
+    /// Enables the JSON-aware mode which sanitizes only the string values
+    /// of a JSON document. Disabling it also stops sanitizing the keys.
+    pub fn set_json(&mut self, bjson: bool) {
+        self._bjson = bjson;
+
+        if !bjson {
+            self._bjson_keys = false;
+        }
+    }
+
+    /// Sanitizes also the keys of the objects in the JSON-aware mode.
+    /// This enables the JSON-aware mode.
+    pub fn set_json_keys(&mut self, bkeys: bool) {
+        self._bjson_keys = bkeys;
+
+        if bkeys {
+            self._bjson = true;
+        }
+    }
+
+    /// Enables the CSV-aware mode with the given field delimiter like b',' or b'\t'.
+    pub fn set_csv_delimiter(&mut self, delimiter: u8) {
+        self._ocsv.get_or_insert_with(CsvOptions::csv).delimiter = delimiter;
+    }
+
+    /// Enables the CSV-aware mode with comma separated values or disables it.
+    pub fn set_csv(&mut self, bcsv: bool) {
+        if bcsv {
+            self.set_csv_delimiter(b',');
+        } else {
+            self._ocsv = None;
+        }
+    }
+
+    /// Selects the sanitized columns of a list like "2,4-6" in the CSV-aware mode.\
+    /// The columns are counted from 1. This enables the CSV-aware mode.
+    pub fn add_column_list(&mut self, slist: &str) -> i32 {
+        match parse_column_list(slist) {
+            Ok(vcolumns) => {
+                let options = self._ocsv.get_or_insert_with(CsvOptions::csv);
+
+                for icolumn in vcolumns {
+                    if !options.columns.contains(&icolumn) {
+                        options.columns.push(icolumn);
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("column list '{}': {}", slist, e);
+
+                //Set Parameter Error
+                self.set_error_code(EXIT_USAGE_ERROR);
+            }
+        } //match parse_column_list(slist)
+
+        self._ierr
+    }
+
+    /// Removes the selected columns. So all columns are sanitized in the CSV-aware mode.
+    pub fn clear_column_list(&mut self) {
+        if let Some(options) = self._ocsv.as_mut() {
+            options.columns.clear();
+        }
+    }
+
+    /// Enables the diff report which shows the changed lines with markers
+    /// at the replacements instead of the sanitized text.
+    pub fn set_diff(&mut self, bdiff: bool) {
+        self._bdiff = bdiff;
+    }
+
+    /// Enables the strict mode which fails when any character stays unmapped
+    /// or any invalid byte is found. The sanitized data is not written then.
+    pub fn set_strict(&mut self, bstrict: bool) {
+        self._bstrict = bstrict;
+    }
+
+    /// Reads the whole input before it is sanitized instead of streaming it line by line.\
+    /// The HTML, JSON, CSV and diff modes, files and output files always use the batch mode.
+    pub fn set_batch(&mut self, bbatch: bool) {
+        self._bbatch = bbatch;
+    }
+
+    /// Limits the size of the input in the batch mode to a size like "512", "64K" or "10M".\
+    /// Larger input is rejected as I/O error.
+    pub fn set_max_input_size(&mut self, ssize: &str) -> i32 {
+        match parse_size(ssize) {
+            Ok(isize) => self._omax_input = Some(isize),
+            Err(e) => {
+                eprintln!("max input size '{}': {}", ssize, e);
+
+                //Set Parameter Error
+                self.set_error_code(EXIT_USAGE_ERROR);
+            }
+        } //match parse_size(ssize)
+
+        self._ierr
+    }
+
+    /// Sets the command with its arguments which is run in the command wrapper mode.
+    /// Its STDOUT and STDERR are sanitized instead of STDIN.
+    pub fn set_command(&mut self, vcommand: &[String]) {
+        self._vcommand = vcommand.to_vec();
+    }
+
+    /// Writes the sanitized STDERR of the command to STDOUT.
+    pub fn set_merge(&mut self, bmerge: bool) {
+        self._ocommand.merge = bmerge;
+    }
+
+    /// Writes the label "stdout: " or "stderr: " at the start of each line of the command.
+    pub fn set_label(&mut self, blabel: bool) {
+        self._ocommand.label = blabel;
+    }
+
+    /// Runs the command under a pseudo-terminal. So it prints its output like in a terminal.
+    /// The keystrokes and the terminal size changes are passed on to the command.\
+    /// This is only available on Linux.
+    pub fn set_pty(&mut self, bpty: bool) {
+        self._bpty = bpty;
+    }
+
+    /// Removes the escape sequences of the command under a pseudo-terminal as a whole
+    /// instead of keeping them intact.
+    pub fn set_strip_escapes(&mut self, bstrip: bool) {
+        self._bstrip_escapes = bstrip;
+    }
+
+    /// Adds a file which is read instead of STDIN.
+    pub fn add_input_file(&mut self, sfile: &str) {
+        self._vfiles.push(PathBuf::from(sfile));
+    }
+
+    /// Writes the output into a file instead of STDOUT.
+    pub fn set_output_file(&mut self, sfile: &str) -> i32 {
+        if sfile.is_empty() {
+            eprintln!("output file: no file given");
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+        } else {
+            self._ooutput = Some(PathBuf::from(sfile));
+        }
+
+        self._ierr
+    }
+
+    /// Writes the output back into the input files.\
+    /// With a non-empty `suffix` the original file is kept as backup with this suffix.
+    pub fn set_in_place(&mut self, suffix: &str) {
+        self._oinplace = Some(suffix.to_string());
+    }
+
+    /// Runs the application as service which answers HTTP requests on a localhost address
+    /// like "127.0.0.1:8750".
+    pub fn set_listen(&mut self, saddress: &str) {
+        self._olisten = Some(saddress.to_string());
+    }
+
+    /// Runs the application as service which answers HTTP requests on a Unix domain socket.
+    pub fn set_socket(&mut self, sfile: &str) {
+        self._osocket = Some(PathBuf::from(sfile));
+    }
+
+    /// Adds a language shortcode like "de" to the applied Language Replacement Maps.\
+    /// Languages without a `LanguageMap` are reported as error.
+    pub fn add_request_language(&mut self, slanguage: &str) -> i32 {
+        if !self._sanitizer.has_language_map(slanguage) {
+            eprintln!("language '{}': no language map", slanguage);
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+
+            return self._ierr;
+        }
+
         let slang = String::from(slanguage);
 
         if !self._vrqlangs.contains(&slang) {
@@ -215,12 +490,167 @@ impl RunTextSanitizer {
         }
 
         self._sanitizer.add_request_language(slanguage);
+
+        self._ierr
+    }
+
+    /// Removes the requested languages. So only "en" stays applied.
+    pub fn clear_request_languages(&mut self) {
+        self._vrqlangs.clear();
+        self._sanitizer.clear_request_languages();
+
+        self.init();
+    }
+
+    /// Adds the languages of the GNU list "LANGUAGE" and the language of the first locale
+    /// environment variable of "LC_ALL", "LC_MESSAGES" and "LANG" like "es_ES.UTF-8"
+    /// when the application runs. "en" stays the last fallback.
+    pub fn set_auto_lang(&mut self, bauto: bool) {
+        self._bauto_lang = bauto;
+    }
+
+    /// Adds the characters of a list like "U+20AC,b0,°,U+2010-U+2015" to the allowlist.\
+    /// Codepoints are given in hexadecimal with an optional "U+" or "0x" prefix.
+    /// Single characters are taken literally.
+    pub fn add_keep_list(&mut self, slist: &str) -> i32 {
+        match parse_keep_list(slist) {
+            Ok(vranges) => {
+                for (first, last) in vranges {
+                    self._sanitizer.add_keep_range(first, last);
+                }
+            }
+            Err(e) => {
+                eprintln!("keep list '{}': {}", slist, e);
+
+                //Set Parameter Error
+                self.set_error_code(EXIT_USAGE_ERROR);
+            }
+        } //match parse_keep_list(slist)
+
+        self._ierr
+    }
+
+    /// Removes all characters from the allowlist.
+    pub fn clear_keep_list(&mut self) {
+        self._sanitizer.clear_keep_characters();
+    }
+
+    /// Adds or replaces the `LanguageMap`s of a JSON file by language shortcode like
+    /// `{"fi": {"e4": "a"}, "de": {"fc": "ue"}}`.
+    pub fn load_map_file(&mut self, path: &Path) -> i32 {
+        let result = fs::read(path)
+            .map_err(|e| (EXIT_IO_ERROR, e.to_string()))
+            .and_then(|vmap| {
+                serde_json::from_slice::<ConversionMap>(&vmap)
+                    .map_err(|e| (EXIT_USAGE_ERROR, e.to_string()))
+            });
+
+        match result {
+            Ok(conv_map) => {
+                for (slang, lngmap) in conv_map.0 {
+                    self._sanitizer.set_language_map(&slang, lngmap);
+                }
+            }
+            Err((ierr, e)) => {
+                eprintln!("map file '{}': {}", path.display(), e);
+
+                self.set_error_code(ierr);
+            }
+        } //match result
+
+        self._ierr
+    }
+
+    /// Sets how characters without any mapping are handled.
+    pub fn set_fallback(&mut self, fallback: Fallback) {
+        self._sanitizer.set_fallback(fallback);
+    }
+
+    /// Sets the fallback of a mode like "codepoint", "remove" or "replace"
+    /// with its `oreplacement`.\
+    /// Unknown modes are reported as error.
+    pub fn set_fallback_mode(&mut self, smode: &str, oreplacement: Option<&str>) -> i32 {
+        match parse_fallback(smode, oreplacement) {
+            Ok(fallback) => self.set_fallback(fallback),
+            Err(e) => {
+                eprintln!("fallback: {}", e);
+
+                //Set Parameter Error
+                self.set_error_code(EXIT_USAGE_ERROR);
+            }
+        } //match parse_fallback(smode, oreplacement)
+
+        self._ierr
+    }
+
+    /// Applies the settings of the configuration file or of one of its profiles.\
+    /// The settings are applied before the command line parameters which override them.
+    pub fn apply_config(&mut self, config: &Config) -> i32 {
+        //The Map Files can provide the requested Languages
+        for file in config.map_files.iter().flatten() {
+            self.load_map_file(file);
+        }
+
+        for slanguage in config.languages.iter().flatten() {
+            self.add_request_language(slanguage);
+        }
+
+        if let Some(smode) = &config.fallback {
+            self.set_fallback_mode(smode, config.replacement.as_deref());
+        }
+
+        if let Some(slist) = &config.keep {
+            self.add_keep_list(slist);
+        }
+
+        self.set_auto_lang(config.auto_lang.unwrap_or(false));
+
+        if config.csv == Some(true) {
+            self.set_csv_delimiter(b',');
+        }
+
+        if config.tsv == Some(true) {
+            self.set_csv_delimiter(b'\t');
+        }
+
+        if let Some(slist) = &config.columns {
+            self.add_column_list(slist);
+        }
+
+        self.set_preserve_width(config.preserve_width.unwrap_or(false));
+        self.set_html(config.html.unwrap_or(false));
+        self.set_json(config.json.unwrap_or(false));
+        self.set_json_keys(config.json_keys.unwrap_or(false));
+        self.set_diff(config.diff.unwrap_or(false));
+        self.set_strict(config.strict.unwrap_or(false));
+
+        self._ierr
     }
 
     fn init(&mut self) {
         self.add_request_language(&"en");
     }
 
+    fn add_system_languages(&mut self) {
+        let vlangs = self._sanitizer.add_system_languages();
+
+        for slang in vlangs {
+            if !self._vrqlangs.contains(&slang) {
+                self._vrqlangs.push(slang);
+            }
+        }
+
+        //"en" is the last Fallback
+        let slang = String::from("en");
+
+        remove_match(&mut self._vrqlangs, &slang);
+        self._vrqlangs.push(slang);
+
+        if self._bdebug && !self._bquiet {
+            eprintln!("auto languages: {:?}", self._vrqlangs);
+        }
+    }
+
     fn set_input(&mut self, mut vinput: Vec<u8>) {
         self._vinput = vinput.drain(0..).collect();
     }
@@ -239,23 +669,66 @@ impl RunTextSanitizer {
         //      }
         //    } //if bprfg
 
-        match stdin.read_to_end(&mut self._vinput) {
-            Ok(_) => {}
+        match read_limited(&mut stdin, self._omax_input) {
+            Ok(vinput) => self._vinput = vinput,
             Err(e) => {
-                if !self._bquiet {
-                    eprintln!("{}", &format!("msg: '{:?}'", e));
-                }
+                eprintln!("input: {}", e);
 
                 //Clear the invalid Input
                 self._vinput.clear();
                 //Set Execution Error
-                self._ierr = 1;
+                self.set_error_code(EXIT_IO_ERROR);
             }
-        } //match stdin.read_to_end(&mut self._vinput)
+        } //match read_limited(&mut stdin, self._omax_input)
 
         self._ierr
     }
 
+    /// Keeps the first error code. So the cause of the failure is not overwritten.
+    fn set_error_code(&mut self, ierr: i32) {
+        if self._ierr == EXIT_OK {
+            self._ierr = ierr;
+        }
+    }
+
+    /// Sanitizes the input in the selected mode.\
+    /// Errors are returned with their exit code.
+    fn sanitize_input(&mut self) -> Result<(), (i32, String)> {
+        if self._bstrict {
+            check_strict(&self._sanitizer, &self._vinput).map_err(|e| match e {
+                StrictViolation::Unmapped(_, _) => (EXIT_UNMAPPED, format!("strict: {}", e)),
+                StrictViolation::InvalidByte(_, _) => {
+                    (EXIT_INVALID_ENCODING, format!("strict: {}", e))
+                }
+            })?;
+        }
+
+        if self._bdiff {
+            self._srsout = build_diff_report(&self._sanitizer, &self._vinput);
+        } else if let Some(options) = &self._ocsv {
+            self._srsout = self._sanitizer.sanitize_csv_u8(&self._vinput, options);
+        } else if self._bjson {
+            self._srsout = self
+                ._sanitizer
+                .sanitize_json_u8(&self._vinput, self._bjson_keys)
+                .map_err(|e| (EXIT_INVALID_FORMAT, format!("json: {}", e)))?;
+        } else if self._bhtml {
+            //The Markup cannot be split into Chunks
+            self._srsout = self._sanitizer.sanitize_html_u8(&self._vinput);
+        } else {
+            #[cfg(feature = "parallel")]
+            {
+                self._srsout = self._sanitizer.sanitize_u8_parallel(&self._vinput);
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                self._srsout = self._sanitizer.sanitize_u8(&self._vinput);
+            }
+        } //if self._bhtml
+
+        Ok(())
+    }
+
     fn do_sanitze(&mut self) -> i32 {
         //-------------------------------------
         //Parse the Input Data
@@ -269,7 +742,13 @@ impl RunTextSanitizer {
         //    }
         //  }  //if self._bprofiling
 
-        self._srsout = self._sanitizer.sanitize_u8(&self._vinput);
+        if let Err((ierr, e)) = self.sanitize_input() {
+            eprintln!("{}", e);
+
+            self._srsout.clear();
+            //Set Input Error
+            self.set_error_code(ierr);
+        }
 
         //  if self._bprofiling {
         //    match duration_parse.elapsed() {
@@ -288,12 +767,237 @@ impl RunTextSanitizer {
         self._ierr
     }
 
+    /// Sanitizes a single file and writes it back atomically.
+    fn sanitize_file_in_place(&mut self, path: &Path, suffix: &str) -> Result<(), (i32, String)> {
+        self._vinput =
+            read_file(path, self._omax_input).map_err(|e| (EXIT_IO_ERROR, e.to_string()))?;
+
+        self.sanitize_input()?;
+
+        if !suffix.is_empty() {
+            fs::copy(path, build_backup_path(path, suffix))
+                .map_err(|e| (EXIT_IO_ERROR, format!("backup failed: {}", e)))?;
+        }
+
+        write_atomic(path, self._srsout.as_bytes()).map_err(|e| (EXIT_IO_ERROR, e.to_string()))
+    }
+
+    fn do_run_in_place(&mut self, suffix: &str) -> i32 {
+        if self._vfiles.is_empty() {
+            eprintln!("in-place: no files given");
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+        } else if self._ooutput.is_some() {
+            eprintln!("in-place: cannot be combined with an output file");
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+        } else {
+            for path in self._vfiles.clone() {
+                if let Err((ierr, e)) = self.sanitize_file_in_place(&path, suffix) {
+                    eprintln!("file '{}': {}", path.display(), e);
+
+                    //Set Execution Error
+                    self.set_error_code(ierr);
+                }
+            }
+        } //if self._vfiles.is_empty()
+
+        self._ierr
+    }
+
+    fn input_from_files(&mut self) -> i32 {
+        let mut srsout = String::new();
+
+        //Each File is sanitized on its own
+        for path in self._vfiles.clone() {
+            let result = read_file(&path, self._omax_input)
+                .map_err(|e| (EXIT_IO_ERROR, e.to_string()))
+                .and_then(|vinput| {
+                    self._vinput = vinput;
+                    self.sanitize_input()
+                });
+
+            match result {
+                Ok(_) => srsout.push_str(&self._srsout),
+                Err((ierr, e)) => {
+                    eprintln!("file '{}': {}", path.display(), e);
+
+                    //Set Execution Error
+                    self.set_error_code(ierr);
+                }
+            } //match result
+        } //for path in self._vfiles.clone()
+
+        self._srsout = srsout;
+
+        self._ierr
+    }
+
+    /// Sanitizes STDIN line by line and writes each line as soon as it is complete.
+    fn do_run_streaming(&mut self) -> i32 {
+        let result = {
+            let mut streamer = StreamSanitizer::new(&self._sanitizer, self._bstrict);
+            let stdout = io::stdout();
+            let mut writer = stdout.lock();
+
+            sanitize_stream(
+                &mut streamer,
+                io::stdin(),
+                &mut writer,
+                Duration::from_millis(FLUSH_TIMEOUT_MS),
+            )
+        };
+
+        if let Err((ierr, e)) = result {
+            eprintln!("{}", e);
+
+            //Set Execution Error
+            self.set_error_code(ierr);
+        }
+
+        self._ierr
+    }
+
+    #[cfg(target_os = "linux")]
+    fn run_command_pty(&self) -> Result<i32, (i32, String)> {
+        let stdout = io::stdout();
+
+        run_pty(
+            &self._sanitizer,
+            &self._vcommand,
+            self._bstrip_escapes,
+            Some(libc::STDIN_FILENO),
+            &mut stdout.lock(),
+        )
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn run_command_pty(&self) -> Result<i32, (i32, String)> {
+        Err((
+            EXIT_USAGE_ERROR,
+            String::from("pty: only available on Linux"),
+        ))
+    }
+
+    /// Answers the sanitize requests until the application is stopped.
+    fn do_run_serve(&mut self) -> i32 {
+        let service = SanitizerService::new(&self._sanitizer, self._omax_input);
+
+        if !self._bquiet {
+            if let Some(saddress) = &self._olisten {
+                eprintln!("serve: listening on 'http://{}'", saddress);
+            }
+
+            if let Some(path) = &self._osocket {
+                eprintln!("serve: listening on socket '{}'", path.display());
+            }
+        } //if !self._bquiet
+
+        if let Err((ierr, e)) =
+            run_service(service, self._olisten.as_deref(), self._osocket.as_deref())
+        {
+            eprintln!("{}", e);
+
+            //Set Execution Error
+            self.set_error_code(ierr);
+        }
+
+        self._ierr
+    }
+
+    /// Runs the command and exits with its exit code.
+    fn do_run_command(&mut self) -> i32 {
+        if !self._vfiles.is_empty() || self._oinplace.is_some() || self._ooutput.is_some() {
+            eprintln!("command: cannot be combined with files");
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+
+            return self._ierr;
+        }
+
+        self._ocommand.strict = self._bstrict;
+
+        let stdout = io::stdout();
+        let stderr = io::stderr();
+
+        let result = if self._bpty {
+            self.run_command_pty()
+        } else {
+            run_command(
+                &self._sanitizer,
+                &self._vcommand,
+                &self._ocommand,
+                Duration::from_millis(FLUSH_TIMEOUT_MS),
+                &mut stdout.lock(),
+                &mut stderr.lock(),
+            )
+        };
+
+        match result {
+            Ok(icode) => self.set_error_code(icode),
+            Err((ierr, e)) => {
+                eprintln!("{}", e);
+
+                //Set Execution Error
+                self.set_error_code(ierr);
+            }
+        } //match result
+
+        self._ierr
+    }
+
     pub fn do_run(&mut self) -> i32 {
-        if self._bimport {
-            self.input_from_stdin();
+        if self._bauto_lang {
+            self.add_system_languages();
         }
 
-        self.do_sanitze();
+        if self.is_serving() {
+            return self.do_run_serve();
+        }
+
+        if self._bdiff && (self._bhtml || self._bjson || self._ocsv.is_some()) {
+            eprintln!("diff: cannot be combined with the HTML, JSON or CSV mode");
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+
+            return self._ierr;
+        }
+
+        if self._bdiff && self._oinplace.is_some() {
+            //The Report would replace the Content of the Files
+            eprintln!("diff: cannot be combined with the in-place mode");
+
+            //Set Parameter Error
+            self.set_error_code(EXIT_USAGE_ERROR);
+
+            return self._ierr;
+        }
+
+        if !self._vcommand.is_empty() {
+            return self.do_run_command();
+        }
+
+        if let Some(suffix) = self._oinplace.clone() {
+            return self.do_run_in_place(&suffix);
+        }
+
+        if self.is_streaming() {
+            return self.do_run_streaming();
+        }
+
+        if !self._vfiles.is_empty() {
+            self.input_from_files();
+        } else {
+            if self._bimport {
+                self.input_from_stdin();
+            }
+
+            self.do_sanitze();
+        } //if !self._vfiles.is_empty()
 
         //  if(bdbg && ! bqt) {
         //    println!("sleeping 30 secs: go ...");
@@ -304,7 +1008,10 @@ impl RunTextSanitizer {
         //    println!("sleeping 30 secs: done.");
         //  }  //if(bdbg && ! bqt)
 
-        self.output_to_stdout();
+        match self._ooutput.clone() {
+            Some(path) => self.output_to_file(&path),
+            None => self.output_to_stdout(),
+        };
 
         self._ierr
     }
@@ -329,10 +1036,81 @@ impl RunTextSanitizer {
         self._bprofiling
     }
 
+    pub fn is_html(&self) -> bool {
+        self._bhtml
+    }
+
+    pub fn is_json(&self) -> bool {
+        self._bjson
+    }
+
+    pub fn is_csv(&self) -> bool {
+        self._ocsv.is_some()
+    }
+
+    /// Checks whether STDIN is sanitized line by line in the streaming mode.\
+    /// The preserved display width needs whole lines. So it uses the batch mode.
+    pub fn is_streaming(&self) -> bool {
+        self._bimport
+            && !self._bbatch
+            && !self._sanitizer.is_preserve_width()
+            && self._vfiles.is_empty()
+            && self._ooutput.is_none()
+            && !self._bhtml
+            && !self._bjson
+            && self._ocsv.is_none()
+            && !self._bdiff
+    }
+
+    /// Checks whether the application runs as service in the serve mode.
+    pub fn is_serving(&self) -> bool {
+        self._olisten.is_some() || self._osocket.is_some()
+    }
+
+    pub fn get_command(&self) -> &[String] {
+        &self._vcommand
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self._bstrict
+    }
+
+    pub fn is_diff(&self) -> bool {
+        self._bdiff
+    }
+
+    pub fn has_request_language(&self, slanguage: &str) -> bool {
+        self._vrqlangs.contains(&String::from(slanguage))
+    }
+
+    pub fn has_language_map(&self, slanguage: &str) -> bool {
+        self._sanitizer.has_language_map(slanguage)
+    }
+
+    pub fn is_auto_lang(&self) -> bool {
+        self._bauto_lang
+    }
+
+    /// Returns the language shortcodes of all available `LanguageMap`s.
+    pub fn get_languages(&self) -> Vec<String> {
+        self._sanitizer.get_languages()
+    }
+
     pub fn get_output(&self) -> &str {
         self._srsout.as_str()
     }
 
+    fn output_to_file(&mut self, path: &Path) -> i32 {
+        if let Err(e) = write_atomic(path, self._srsout.as_bytes()) {
+            eprintln!("output file '{}': {}", path.display(), e);
+
+            //Set Execution Error
+            self.set_error_code(EXIT_IO_ERROR);
+        }
+
+        self._ierr
+    }
+
     fn output_to_stdout(&self) -> i32 {
         //        let data = self._importer.export_accounts_str();
 
@@ -349,6 +1127,145 @@ impl RunTextSanitizer {
 //==============================================================================
 // Auxiliary Functions
 
+fn parse_keep_character(sitem: &str) -> Result<char, String> {
+    let mut chars = sitem.chars();
+
+    //A single Character is taken literally
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(c);
+    }
+
+    let scode = if sitem.starts_with("U+") || sitem.starts_with("u+") || sitem.starts_with("0x") {
+        &sitem[2..]
+    } else {
+        sitem
+    };
+
+    match u32::from_str_radix(scode, 16) {
+        Ok(icode) => match std::char::from_u32(icode) {
+            Some(c) => Ok(c),
+            None => Err(format!("'{}' is not a valid codepoint", sitem)),
+        },
+        Err(_) => Err(format!("'{}' is not a character or codepoint", sitem)),
+    }
+}
+
+pub(crate) fn parse_keep_list(slist: &str) -> Result<Vec<(char, char)>, String> {
+    let mut vranges = Vec::new();
+
+    for sitem in slist.split(',').map(|s| s.trim()) {
+        if sitem.is_empty() {
+            continue;
+        }
+
+        //A Range needs Characters on both sides of the Dash
+        let orange = match sitem.find('-') {
+            Some(idash) if idash > 0 && idash < sitem.len() - 1 => {
+                Some((&sitem[..idash], &sitem[(idash + 1)..]))
+            }
+            _ => None,
+        };
+
+        match orange {
+            Some((sfirst, slast)) => {
+                vranges.push((parse_keep_character(sfirst)?, parse_keep_character(slast)?))
+            }
+            None => {
+                let c = parse_keep_character(sitem)?;
+
+                vranges.push((c, c));
+            }
+        } //match orange
+    } //for sitem in slist.split(',')
+
+    if vranges.is_empty() {
+        return Err("no characters given".to_string());
+    }
+
+    Ok(vranges)
+}
+
+/// Parses a size like "512", "64K", "10M" or "1G" into bytes.
+fn parse_size(ssize: &str) -> Result<u64, String> {
+    let sdigits = ssize.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let ifactor: u64 = match &ssize[sdigits.len()..].to_lowercase()[..] {
+        "" | "b" => 1,
+        "k" => 1024,
+        "m" => 1024 * 1024,
+        "g" => 1024 * 1024 * 1024,
+        sunit => return Err(format!("unknown unit '{}'", sunit)),
+    };
+
+    match sdigits.parse::<u64>() {
+        Ok(isize) => isize
+            .checked_mul(ifactor)
+            .ok_or_else(|| String::from("size is too large")),
+        Err(_) => Err(String::from("size must be a number")),
+    }
+}
+
+/// Reads the whole input but fails when it is larger than `omax` bytes.
+fn read_limited<R: Read>(reader: R, omax: Option<u64>) -> io::Result<Vec<u8>> {
+    let mut vinput = Vec::new();
+
+    match omax {
+        Some(imax) => {
+            //One Byte more tells whether the Input is too large
+            reader.take(imax + 1).read_to_end(&mut vinput)?;
+
+            if vinput.len() as u64 > imax {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("input exceeds the maximum size of {} bytes", imax),
+                ));
+            }
+        }
+        None => {
+            let mut reader = reader;
+
+            reader.read_to_end(&mut vinput)?;
+        }
+    } //match omax
+
+    Ok(vinput)
+}
+
+fn read_file(path: &Path, omax: Option<u64>) -> io::Result<Vec<u8>> {
+    read_limited(fs::File::open(path)?, omax)
+}
+
+fn parse_column_list(slist: &str) -> Result<Vec<usize>, String> {
+    let mut vcolumns = Vec::new();
+
+    for sitem in slist.split(',').map(|s| s.trim()) {
+        if sitem.is_empty() {
+            continue;
+        }
+
+        let (sfirst, slast) = match sitem.find('-') {
+            Some(idash) => (&sitem[..idash], &sitem[(idash + 1)..]),
+            None => (sitem, sitem),
+        };
+
+        //The Columns are counted from 1
+        match (
+            sfirst.trim().parse::<usize>(),
+            slast.trim().parse::<usize>(),
+        ) {
+            (Ok(ifirst), Ok(ilast)) if ifirst > 0 && ifirst <= ilast => {
+                vcolumns.extend((ifirst - 1)..ilast);
+            }
+            _ => return Err(format!("'{}' is not a column or range of columns", sitem)),
+        }
+    } //for sitem in slist.split(',')
+
+    if vcolumns.is_empty() {
+        return Err("no columns given".to_string());
+    }
+
+    Ok(vcolumns)
+}
+
 fn remove_match<T: PartialEq>(vvector: &mut Vec<T>, search: &T) -> Option<usize> {
     let mut iter = vvector.iter_mut();
     let mut oitem = iter.next();
@@ -399,3 +1316,258 @@ fn app_sparkle_heart() {
 
     assert_eq!(srsout, "<3");
 }
+
+#[test]
+fn app_keep_list() {
+    assert_eq!(
+        parse_keep_list("U+20AC, b0,\u{2764},0x2010-u+2015"),
+        Ok(vec![
+            ('\u{20ac}', '\u{20ac}'),
+            ('\u{b0}', '\u{b0}'),
+            ('\u{2764}', '\u{2764}'),
+            ('\u{2010}', '\u{2015}')
+        ])
+    );
+    assert_eq!(parse_keep_list("-"), Ok(vec![('-', '-')]));
+    assert!(parse_keep_list("U+D800").is_err());
+    assert!(parse_keep_list("euro").is_err());
+    assert!(parse_keep_list(",").is_err());
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(app.add_keep_list("\u{b0},20ac"), 0);
+
+    app.set_input("21\u{b0}C 5\u{20ac} \u{2764}".as_bytes().to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "21\u{b0}C 5\u{20ac} <3");
+}
+
+#[test]
+fn app_html() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("de");
+    app.set_html(true);
+    app.set_input(b"<p title=\"Gr&uuml;&szlig;e\">&lt;3 \xe2\x9d\xa4</p>".to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "<p title=\"Gruesse\">&lt;3 &lt;3</p>");
+}
+
+#[test]
+fn app_json() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("es");
+    app.set_json(true);
+    app.set_input(
+        b"{\"stdout\": \"Acci\\u00f3n\\n\xc2\xabD\xc3\xada\xc2\xbb\", \"rc\": 0}".to_vec(),
+    );
+    app.do_sanitze();
+
+    assert_eq!(
+        app.get_output(),
+        "{\"stdout\": \"Accion\\n\\\"Dia\\\"\", \"rc\": 0}"
+    );
+    assert_eq!(app.get_error_code(), 0);
+
+    app.set_input(b"{\"stdout\": ".to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "");
+    assert_eq!(app.get_error_code(), EXIT_INVALID_FORMAT);
+}
+
+#[test]
+fn app_csv() {
+    assert_eq!(parse_column_list("2, 4-6"), Ok(vec![1, 3, 4, 5]));
+    assert!(parse_column_list("0").is_err());
+    assert!(parse_column_list("3-1").is_err());
+    assert!(parse_column_list("a").is_err());
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("de");
+
+    assert_eq!(app.add_column_list("2"), 0);
+
+    app.set_csv_delimiter(b';');
+    app.set_input(
+        "Gr\u{fc}n;\"Gr\u{fc}n; Rot\";\u{2764}\n"
+            .as_bytes()
+            .to_vec(),
+    );
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "Gr\u{fc}n;\"Gruen; Rot\";\u{2764}\n");
+}
+
+#[test]
+fn app_diff_modes() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.set_diff(true);
+    app.set_json(true);
+
+    assert_eq!(app.do_run(), EXIT_USAGE_ERROR);
+}
+
+#[test]
+fn app_streaming_width() {
+    let mut app = RunTextSanitizer::new_with_options(true, true, false, false);
+
+    assert!(app.is_streaming());
+
+    //The preserved Width needs whole Lines
+    app.set_preserve_width(true);
+
+    assert!(!app.is_streaming());
+}
+
+#[test]
+fn app_diff_in_place() {
+    let path = std::env::temp_dir().join(format!("text-sanitizer_diff_{}.txt", std::process::id()));
+
+    fs::write(&path, "Gr\u{fc}n\n").expect("test file not written");
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("de");
+    app.set_diff(true);
+    app.set_in_place("");
+    app.add_input_file(&path.to_string_lossy());
+
+    //The File is never replaced by the Report
+    assert_eq!(app.do_run(), EXIT_USAGE_ERROR);
+    assert_eq!(
+        fs::read_to_string(&path).expect("test file not read"),
+        "Gr\u{fc}n\n"
+    );
+
+    fs::remove_file(&path).expect("test file not removed");
+}
+
+#[test]
+fn app_strict() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("de");
+    app.set_strict(true);
+    app.set_input("Gr\u{fc}n \u{2764}\n".as_bytes().to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "Gruen <3\n");
+    assert_eq!(app.get_error_code(), EXIT_OK);
+
+    app.set_input(b"Gr\xc3\xbcn \xc3\xb1\n\xfc".to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "");
+    assert_eq!(app.get_error_code(), EXIT_UNMAPPED);
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.set_strict(true);
+    app.set_input(b"Gr\xfcn".to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_error_code(), EXIT_INVALID_ENCODING);
+}
+
+#[test]
+fn app_files() {
+    let dir = std::env::temp_dir().join(format!("text-sanitizer_app_{}", std::process::id()));
+    let path = dir.join("input.txt");
+    let output = dir.join("output.txt");
+
+    fs::create_dir_all(&dir).expect("test directory not created");
+    fs::write(&path, "Gr\u{fc}n\n").expect("test file not written");
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("de");
+    app.add_input_file(&path.to_string_lossy());
+    app.add_input_file(&dir.join("missing.txt").to_string_lossy());
+    app.set_output_file(&output.to_string_lossy());
+
+    //The missing File is reported but the other File is written
+    assert_eq!(app.do_run(), 1);
+    assert_eq!(fs::read(&output).expect("output not readable"), b"Gruen\n");
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    app.add_request_language("de");
+    app.add_input_file(&path.to_string_lossy());
+    app.set_in_place(".bak");
+
+    assert_eq!(app.do_run(), 0);
+    assert_eq!(fs::read(&path).expect("file not readable"), b"Gruen\n");
+    assert_eq!(
+        fs::read(dir.join("input.txt.bak")).expect("backup not readable"),
+        "Gr\u{fc}n\n".as_bytes()
+    );
+
+    fs::remove_dir_all(&dir).expect("test directory not removed");
+}
+
+#[test]
+fn app_input_size() {
+    assert_eq!(parse_size("512"), Ok(512));
+    assert_eq!(parse_size("64K"), Ok(64 * 1024));
+    assert_eq!(parse_size("10m"), Ok(10 * 1024 * 1024));
+    assert!(parse_size("10x").is_err());
+    assert!(parse_size("M").is_err());
+
+    assert_eq!(
+        read_limited(&b"Gruen"[..], Some(5)).expect("input not read"),
+        b"Gruen"
+    );
+    assert!(read_limited(&b"Gruen\n"[..], Some(5)).is_err());
+    assert_eq!(
+        read_limited(&b"Gruen\n"[..], None).expect("input not read"),
+        b"Gruen\n"
+    );
+}
+
+#[test]
+fn app_config() {
+    let dir = std::env::temp_dir().join(format!("text-sanitizer_config_{}", std::process::id()));
+
+    fs::create_dir_all(&dir).expect("test directory not created");
+    fs::write(dir.join("fi.json"), "{\"fi\": {\"e4\": \"a\"}}").expect("map not written");
+
+    let config = Config {
+        languages: Some(vec![String::from("fi")]),
+        map_files: Some(vec![dir.join("fi.json")]),
+        fallback: Some(String::from("replace")),
+        ..Config::default()
+    };
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(app.apply_config(&config), 0);
+    assert!(app.has_request_language("fi"));
+
+    app.set_input("H\u{e4}n \u{f1}".as_bytes().to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "Han ?");
+
+    //The Fallback of the Parameters overrides the configured one
+    assert_eq!(app.set_fallback_mode("replace", Some("_")), 0);
+
+    app.set_input("H\u{e4}n \u{f1}".as_bytes().to_vec());
+    app.do_sanitze();
+
+    assert_eq!(app.get_output(), "Han _");
+
+    let config = Config {
+        map_files: Some(vec![dir.join("missing.json")]),
+        ..Config::default()
+    };
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(app.apply_config(&config), EXIT_IO_ERROR);
+
+    fs::remove_dir_all(&dir).expect("test directory not removed");
+}
@@ -0,0 +1,312 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage command.rs
+
+* This module implements the command wrapper mode of the Text-Sanitizer application
+* It runs a program and sanitizes its STDOUT and STDERR while they are written
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+*/
+
+use std::io::{self, Read, Write};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use text_sanitizer::TextSanitizer;
+
+use crate::app::{EXIT_COMMAND_NOT_RUN, EXIT_IO_ERROR};
+use crate::stream::StreamSanitizer;
+
+//==============================================================================
+// Constants
+
+/// Size of the chunks which are read from the output of the command
+const COMMAND_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Labels of the lines of STDOUT and STDERR
+const COMMAND_LABELS: [&str; 2] = ["stdout: ", "stderr: "];
+
+//==============================================================================
+// Structure CommandOptions Declaration
+
+/// Describes how the output of the command is written.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CommandOptions {
+    /// checks each line in the strict mode before it is written.
+    pub strict: bool,
+    /// writes the sanitized STDERR of the command to STDOUT.
+    pub merge: bool,
+    /// writes the label "stdout: " or "stderr: " at the start of each line.
+    /// In the merge mode an incomplete line is ended when the other stream writes.
+    pub label: bool,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Reads the output of the command in chunks on its own thread.\
+/// The chunks are sent with the index of the stream. An empty chunk marks the end.
+fn spawn_reader<R>(istream: usize, mut reader: R, sender: Sender<(usize, io::Result<Vec<u8>>)>)
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || loop {
+        let mut vchunk = vec![0; COMMAND_CHUNK_SIZE];
+
+        match reader.read(&mut vchunk) {
+            Ok(iread) => {
+                vchunk.truncate(iread);
+
+                if sender.send((istream, Ok(vchunk))).is_err() || iread == 0 {
+                    break;
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                let _ = sender.send((istream, Err(e)));
+
+                break;
+            }
+        } //match reader.read(&mut vchunk)
+    });
+}
+
+/// Converts the exit status of the command into an exit code.\
+/// A command which was killed by a signal exits with 128 plus the signal number.
+pub(crate) fn build_exit_code(status: ExitStatus) -> i32 {
+    if let Some(icode) = status.code() {
+        return icode;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(isignal) = status.signal() {
+            return 128 + isignal;
+        }
+    }
+
+    EXIT_IO_ERROR
+}
+
+/// Sanitizes the output of the running command until both streams are closed.
+fn sanitize_output<O, E>(
+    vstreamers: &mut [StreamSanitizer],
+    child: &mut Child,
+    options: &CommandOptions,
+    timeout: Duration,
+    out: &mut O,
+    err: &mut E,
+) -> Result<(), (i32, String)>
+where
+    O: Write,
+    E: Write,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    if let Some(stdout) = child.stdout.take() {
+        spawn_reader(0, stdout, sender.clone());
+    }
+
+    if let Some(stderr) = child.stderr.take() {
+        spawn_reader(1, stderr, sender);
+    }
+
+    loop {
+        match receiver.recv_timeout(timeout) {
+            Ok((istream, Ok(vchunk))) => {
+                let writer: &mut dyn Write = if istream == 0 || options.merge {
+                    &mut *out
+                } else {
+                    &mut *err
+                };
+
+                if options.merge && options.label {
+                    //The Line of the other Stream is ended so each Line keeps its Label
+                    vstreamers[1 - istream].end_line(writer)?;
+                }
+
+                if vchunk.is_empty() {
+                    vstreamers[istream].finish(writer)?;
+                } else {
+                    vstreamers[istream].feed(&vchunk, writer)?;
+                }
+            }
+            Ok((_, Err(e))) => return Err((EXIT_IO_ERROR, format!("command output: {}", e))),
+            Err(RecvTimeoutError::Timeout) => {
+                vstreamers[0].flush_pending(out)?;
+
+                if options.merge {
+                    if options.label && vstreamers[1].has_pending() {
+                        vstreamers[0].end_line(out)?;
+                    }
+
+                    vstreamers[1].flush_pending(out)?;
+                } else {
+                    vstreamers[1].flush_pending(err)?;
+                }
+            }
+            //Both Streams are closed
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        } //match receiver.recv_timeout(timeout)
+    } //loop
+}
+
+/// Runs the command `vcommand` with its arguments and sanitizes its STDOUT and STDERR
+/// on separate streams while they are written.\
+/// Returns the exit code of the command. Errors are returned with their exit code.
+pub fn run_command<O, E>(
+    sanitizer: &TextSanitizer,
+    vcommand: &[String],
+    options: &CommandOptions,
+    timeout: Duration,
+    out: &mut O,
+    err: &mut E,
+) -> Result<i32, (i32, String)>
+where
+    O: Write,
+    E: Write,
+{
+    let mut child = Command::new(&vcommand[0])
+        .args(&vcommand[1..])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            (
+                EXIT_COMMAND_NOT_RUN,
+                format!("command '{}': {}", vcommand[0], e),
+            )
+        })?;
+
+    let mut vstreamers = vec![
+        StreamSanitizer::new(sanitizer, options.strict),
+        StreamSanitizer::new(sanitizer, options.strict),
+    ];
+
+    if options.label {
+        for (streamer, slabel) in vstreamers.iter_mut().zip(COMMAND_LABELS.iter()) {
+            streamer.set_label(slabel);
+        }
+    }
+
+    if let Err(e) = sanitize_output(&mut vstreamers, &mut child, options, timeout, out, err) {
+        //The Command does not run on without its Output
+        let _ = child.kill();
+        let _ = child.wait();
+
+        return Err(e);
+    }
+
+    child
+        .wait()
+        .map(build_exit_code)
+        .map_err(|e| (EXIT_IO_ERROR, format!("command '{}': {}", vcommand[0], e)))
+}
+
+//==============================================================================
+// Unit Tests
+
+#[cfg(unix)]
+#[test]
+fn command_output() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("de");
+
+    let vcommand: Vec<String> = vec![
+        String::from("sh"),
+        String::from("-c"),
+        String::from("printf 'Gr\\303\\274n\\n'; printf 'Rot \\303\\274\\n' >&2; exit 3"),
+    ];
+    let mut voutput: Vec<u8> = Vec::new();
+    let mut verror: Vec<u8> = Vec::new();
+
+    let icode = run_command(
+        &sanitizer,
+        &vcommand,
+        &CommandOptions::default(),
+        Duration::from_millis(100),
+        &mut voutput,
+        &mut verror,
+    )
+    .expect("command not run");
+
+    assert_eq!(icode, 3);
+    assert_eq!(voutput, b"Gruen\n");
+    assert_eq!(verror, b"Rot ue\n");
+
+    let mut voutput: Vec<u8> = Vec::new();
+    let mut verror: Vec<u8> = Vec::new();
+    let options = CommandOptions {
+        merge: true,
+        label: true,
+        ..CommandOptions::default()
+    };
+
+    let vcommand: Vec<String> = vec![
+        String::from("sh"),
+        String::from("-c"),
+        String::from("printf 'Gr\\303\\274n\\n' >&2"),
+    ];
+
+    let icode = run_command(
+        &sanitizer,
+        &vcommand,
+        &options,
+        Duration::from_millis(100),
+        &mut voutput,
+        &mut verror,
+    )
+    .expect("command not run");
+
+    assert_eq!(icode, 0);
+    assert_eq!(voutput, b"stderr: Gruen\n");
+    assert!(verror.is_empty());
+
+    let mut voutput: Vec<u8> = Vec::new();
+
+    let vcommand: Vec<String> = vec![
+        String::from("sh"),
+        String::from("-c"),
+        String::from(
+            "printf 'Gr\\303\\274n'; sleep 0.3; printf 'Rot\\n' >&2; sleep 0.3; printf ' 1\\n'",
+        ),
+    ];
+
+    let icode = run_command(
+        &sanitizer,
+        &vcommand,
+        &options,
+        Duration::from_millis(100),
+        &mut voutput,
+        &mut verror,
+    )
+    .expect("command not run");
+
+    assert_eq!(icode, 0);
+    assert_eq!(
+        String::from_utf8_lossy(&voutput),
+        "stdout: Gruen\nstderr: Rot\nstdout:  1\n"
+    );
+
+    assert_eq!(
+        run_command(
+            &sanitizer,
+            &[String::from("/nonexistent/command")],
+            &options,
+            Duration::from_millis(100),
+            &mut voutput,
+            &mut verror,
+        )
+        .map_err(|e| e.0),
+        Err(EXIT_COMMAND_NOT_RUN)
+    );
+}
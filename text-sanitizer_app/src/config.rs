@@ -0,0 +1,245 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage config.rs
+
+* This module implements the configuration file of the Text-Sanitizer application
+* It reads the default settings and the named profiles from a TOML file
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "serde" must be installed
+* - The Rust crate "toml" must be installed
+*/
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_derive::Deserialize;
+
+use text_sanitizer::Fallback;
+
+//==============================================================================
+// Constants
+
+/// Path of the configuration file within the configuration directory
+const CONFIG_FILE: &str = "text-sanitizer/config.toml";
+
+//==============================================================================
+// Structure Config Declaration
+
+/// Holds the settings of the configuration file or of one of its profiles.\
+/// The keys are named like the long options of the command line.
+/// Settings which are not given are `None`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// language shortcodes like "de" which are applied.
+    pub languages: Option<Vec<String>>,
+    /// adds the languages of the locale like "es_ES.UTF-8".
+    pub auto_lang: Option<bool>,
+    /// JSON files with additional `LanguageMap`s by language shortcode.
+    pub map_files: Option<Vec<PathBuf>>,
+    /// handling of unmapped characters: "codepoint", "remove" or "replace".
+    pub fallback: Option<String>,
+    /// replacement string of the "replace" fallback. It defaults to "?".
+    pub replacement: Option<String>,
+    /// characters which are kept like "U+20AC,b0".
+    pub keep: Option<String>,
+    pub preserve_width: Option<bool>,
+    pub html: Option<bool>,
+    pub json: Option<bool>,
+    pub json_keys: Option<bool>,
+    pub csv: Option<bool>,
+    pub tsv: Option<bool>,
+    /// sanitized columns in the CSV-aware mode like "2,4-6".
+    pub columns: Option<String>,
+    pub diff: Option<bool>,
+    pub strict: Option<bool>,
+    /// named profiles which are selected with "--profile NAME".
+    pub profiles: HashMap<String, Config>,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Finds the default configuration file in "$XDG_CONFIG_HOME" or in "$HOME/.config".
+pub fn find_config_path() -> Option<PathBuf> {
+    let odir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(sdir) if !sdir.is_empty() => Some(PathBuf::from(sdir)),
+        _ => env::var_os("HOME").map(|shome| Path::new(&shome).join(".config")),
+    };
+
+    odir.map(|dir| dir.join(CONFIG_FILE))
+}
+
+/// Parses the TOML text of a configuration file.
+pub fn parse_config(sconfig: &str) -> Result<Config, String> {
+    let config: Config = toml::from_str(sconfig).map_err(|e| e.to_string())?;
+
+    for (sname, profile) in config.profiles.iter() {
+        if !profile.profiles.is_empty() {
+            return Err(format!("profile '{}': profiles cannot be nested", sname));
+        }
+    }
+
+    Ok(config)
+}
+
+/// Reads the configuration file `path`.\
+/// The map files are resolved relative to the directory of the file.
+pub fn read_config(path: &Path) -> Result<Config, String> {
+    let sconfig = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut config = parse_config(&sconfig)?;
+
+    if let Some(dir) = path.parent() {
+        config.resolve_map_files(dir);
+    }
+
+    Ok(config)
+}
+
+/// Reads the configuration file `ofile` or the default configuration file and selects
+/// the profile `oprofile`.\
+/// A missing default configuration file is no error unless a profile is requested.
+pub fn load_config(ofile: Option<&str>, oprofile: Option<&str>) -> Result<Option<Config>, String> {
+    let path = match ofile {
+        Some(sfile) => PathBuf::from(sfile),
+        None => match find_config_path() {
+            Some(path) if path.is_file() => path,
+            _ => {
+                return match oprofile {
+                    Some(sname) => Err(format!("profile '{}': no config file", sname)),
+                    None => Ok(None),
+                }
+            }
+        },
+    };
+
+    read_config(&path)
+        .and_then(|config| config.select_profile(oprofile))
+        .map(Some)
+        .map_err(|e| format!("config '{}': {}", path.display(), e))
+}
+
+/// Parses the fallback `smode` with its `oreplacement` for unmapped characters.
+pub fn parse_fallback(smode: &str, oreplacement: Option<&str>) -> Result<Fallback, String> {
+    match smode {
+        "codepoint" => Ok(Fallback::Codepoint),
+        "remove" => Ok(Fallback::Remove),
+        "replace" => Ok(Fallback::Replace(oreplacement.unwrap_or("?").to_string())),
+        _ => Err(format!("unknown fallback '{}'", smode)),
+    }
+}
+
+//==============================================================================
+// Structure Config Implementation
+
+impl Config {
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Builds the settings of the profile `oprofile` on top of the default settings.\
+    /// Without a profile the default settings are returned.
+    /// An unknown profile is returned as error.
+    pub fn select_profile(&self, oprofile: Option<&str>) -> Result<Config, String> {
+        let sname = match oprofile {
+            Some(sname) => sname,
+            None => {
+                return Ok(Config {
+                    profiles: HashMap::new(),
+                    ..self.clone()
+                })
+            }
+        };
+        let profile = match self.profiles.get(sname) {
+            Some(profile) => profile.clone(),
+            None => return Err(format!("unknown profile '{}'", sname)),
+        };
+
+        Ok(Config {
+            languages: profile.languages.or_else(|| self.languages.clone()),
+            auto_lang: profile.auto_lang.or(self.auto_lang),
+            map_files: profile.map_files.or_else(|| self.map_files.clone()),
+            fallback: profile.fallback.or_else(|| self.fallback.clone()),
+            replacement: profile.replacement.or_else(|| self.replacement.clone()),
+            keep: profile.keep.or_else(|| self.keep.clone()),
+            preserve_width: profile.preserve_width.or(self.preserve_width),
+            html: profile.html.or(self.html),
+            json: profile.json.or(self.json),
+            json_keys: profile.json_keys.or(self.json_keys),
+            csv: profile.csv.or(self.csv),
+            tsv: profile.tsv.or(self.tsv),
+            columns: profile.columns.or_else(|| self.columns.clone()),
+            diff: profile.diff.or(self.diff),
+            strict: profile.strict.or(self.strict),
+            profiles: HashMap::new(),
+        })
+    }
+
+    fn resolve_map_files(&mut self, dir: &Path) {
+        if let Some(vfiles) = self.map_files.as_mut() {
+            for file in vfiles.iter_mut() {
+                *file = dir.join(&file);
+            }
+        }
+
+        for profile in self.profiles.values_mut() {
+            profile.resolve_map_files(dir);
+        }
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn config_profiles() {
+    let config = parse_config(
+        "languages = [\"de\"]
+fallback = \"remove\"
+
+[profiles.html-es]
+languages = [\"es\"]
+html = true
+
+[profiles.systemd-status]
+fallback = \"replace\"
+keep = \"U+25CF\"
+",
+    )
+    .expect("config not parsed");
+
+    let profile = config.select_profile(None).expect("defaults not selected");
+
+    assert_eq!(profile.languages, Some(vec![String::from("de")]));
+    assert!(profile.profiles.is_empty());
+
+    let profile = config
+        .select_profile(Some("html-es"))
+        .expect("profile not selected");
+
+    assert_eq!(profile.languages, Some(vec![String::from("es")]));
+    assert_eq!(profile.fallback, Some(String::from("remove")));
+    assert_eq!(profile.html, Some(true));
+
+    let profile = config
+        .select_profile(Some("systemd-status"))
+        .expect("profile not selected");
+
+    assert_eq!(profile.languages, Some(vec![String::from("de")]));
+    assert_eq!(
+        parse_fallback(profile.fallback.as_ref().unwrap(), None),
+        Ok(Fallback::Replace(String::from("?")))
+    );
+    assert_eq!(
+        config.select_profile(Some("html-de")),
+        Err(String::from("unknown profile 'html-de'"))
+    );
+    assert!(parse_config("langs = [\"de\"]").is_err());
+    assert!(parse_config("[profiles.a.profiles.b]\nhtml = true").is_err());
+}
@@ -0,0 +1,166 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage diff.rs
+
+* This module implements the diff report of the Text-Sanitizer application
+* It shows each changed line with the original and the sanitized text
+* and marks the columns of the replacements
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::str;
+
+use text_sanitizer::TextSanitizer;
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Describes the source of a replacement like "U+00FC 'ü'" or "invalid byte 0xfc".
+fn describe_unit(unit: &[u8]) -> String {
+    match str::from_utf8(unit).ok().and_then(|s| s.chars().next()) {
+        Some(c) if c.is_control() => format!("U+{:04X}", c as u32),
+        Some(c) => format!("U+{:04X} '{}'", c as u32, c),
+        None => format!("invalid byte {:#04x}", unit[0]),
+    }
+}
+
+/// Shows the original line with the control characters escaped.
+fn show_original(line: &[u8]) -> String {
+    String::from_utf8_lossy(line)
+        .chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Builds the diff report of the sanitized text.\
+/// Each changed line is shown with the original text after a "-" and the sanitized text
+/// after a "+". The line below marks the columns of the replacements with "^".
+/// The summary counts the lines and the replacements of each character.\
+/// Each character is sanitized on its own. So the display width is not preserved.
+pub fn build_diff_report(sanitizer: &TextSanitizer, text: &[u8]) -> String {
+    let mut sreport = String::new();
+    let mut mpcounts: BTreeMap<(String, String), usize> = BTreeMap::new();
+    let mut ilines = 0;
+    let mut ichanged = 0;
+
+    let mut vlines: Vec<&[u8]> = text.split(|uc| *uc == b'\n').collect();
+
+    if text.is_empty() || text.ends_with(b"\n") {
+        //The last Line ends with the Newline
+        vlines.pop();
+    }
+
+    for (iline, line) in vlines.into_iter().enumerate() {
+        let mut ssanitized = String::with_capacity(line.len());
+        let mut smarkers = String::new();
+        let mut icolumn = 0;
+        let mut ic = 0;
+
+        ilines += 1;
+
+        while ic < line.len() {
+            let (iunit, iunitlen) = match sanitizer.find_next_unit(line, ic) {
+                Some((iunit, iunitlen, _)) => (iunit, iunitlen),
+                None => (line.len(), 0),
+            };
+
+            //The plain ASCII Characters are kept
+            ssanitized.push_str(&String::from_utf8_lossy(&line[ic..iunit]));
+            icolumn += iunit - ic;
+
+            if iunitlen == 0 {
+                break;
+            }
+
+            let unit = &line[iunit..(iunit + iunitlen)];
+            let srpl = sanitizer.sanitize_u8(unit);
+
+            if srpl.as_bytes() != unit {
+                let irpllen = srpl.chars().count();
+
+                while smarkers.len() < icolumn {
+                    smarkers.push(' ');
+                }
+
+                smarkers.push_str(&"^".repeat(irpllen));
+
+                *mpcounts
+                    .entry((describe_unit(unit), srpl.clone()))
+                    .or_insert(0) += 1;
+            }
+
+            ssanitized.push_str(&srpl);
+            icolumn += srpl.chars().count();
+            ic = iunit + iunitlen;
+        } //while ic < line.len()
+
+        if ssanitized.as_bytes() != line {
+            ichanged += 1;
+
+            let _ = writeln!(sreport, "@@ line {} @@", iline + 1);
+            let _ = writeln!(sreport, "-{}", show_original(line));
+            let _ = writeln!(sreport, "+{}", ssanitized);
+            let _ = writeln!(sreport, " {}", smarkers);
+        }
+    } //for (iline, line) in vlines.into_iter().enumerate()
+
+    let mut vcounts: Vec<((String, String), usize)> = mpcounts.into_iter().collect();
+
+    //The most frequent Replacements first
+    vcounts.sort_by_key(|count| std::cmp::Reverse(count.1));
+
+    let _ = writeln!(sreport, "Summary: {} of {} lines changed", ichanged, ilines);
+
+    for ((ssource, srpl), icount) in vcounts {
+        let _ = writeln!(sreport, "{:>6}x {} -> {:?}", icount, ssource, srpl);
+    }
+
+    sreport
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn diff_report() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("en");
+    sanitizer.add_request_language("de");
+
+    let sreport = build_diff_report(
+        &sanitizer,
+        b"Gr\xc3\xbc\xc3\x9fe \xe2\x9d\xa4\nplain\n\xc3\xbcber \xfa\r\n",
+    );
+
+    assert_eq!(
+        sreport,
+        "@@ line 1 @@\n\
+         -Gr\u{fc}\u{df}e \u{2764}\n\
+         +Gruesse <3\n\
+         \x20  ^^^^  ^^\n\
+         @@ line 3 @@\n\
+         -\u{fc}ber \u{fffd}\\r\n\
+         +ueber (?fa)\n\
+         \x20^^    ^^^^^\n\
+         Summary: 2 of 3 lines changed\n\
+         \x20    2x U+00FC '\u{fc}' -> \"ue\"\n\
+         \x20    1x U+000D -> \"\"\n\
+         \x20    1x U+00DF '\u{df}' -> \"ss\"\n\
+         \x20    1x U+2764 '\u{2764}' -> \"<3\"\n\
+         \x20    1x invalid byte 0xfa -> \"(?fa)\"\n"
+    );
+}
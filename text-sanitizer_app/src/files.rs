@@ -0,0 +1,187 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage files.rs
+
+* This module implements the file output of the Text-Sanitizer application
+* Files are written atomically through a temporary file which is renamed
+*
+*---------------------------------
+* Requirements:
+* - no further Rust crates
+*/
+
+use std::collections::hash_map::RandomState;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+//==============================================================================
+// Constants
+
+/// Attempts to create a temporary file with a new random name
+const TEMP_ATTEMPTS: u32 = 16;
+
+/// Symbolic links which are followed at most to find the target file
+const MAX_LINKS: u32 = 40;
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Builds the path of a temporary file with a random name next to `path`.\
+/// It is in the same directory so that it can be renamed atomically.
+fn build_temp_path(path: &Path) -> PathBuf {
+    let mut sname = OsString::from(".");
+    //The Keys of the Standard Library Hasher are random
+    let irandom = RandomState::new().build_hasher().finish();
+
+    if let Some(name) = path.file_name() {
+        sname.push(name);
+    }
+
+    sname.push(format!(".{}.{:016x}.tmp", process::id(), irandom));
+
+    match path.parent() {
+        Some(dir) => dir.join(sname),
+        None => PathBuf::from(sname),
+    }
+}
+
+/// Builds the path of the backup file by appending `suffix` to the file name.
+pub fn build_backup_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut sbackup = path.as_os_str().to_owned();
+
+    sbackup.push(suffix);
+
+    PathBuf::from(sbackup)
+}
+
+/// Follows the symbolic links of `path` to the file which is replaced.\
+/// So the links stay intact. The target does not need to exist yet.
+fn resolve_link_target(path: &Path) -> io::Result<PathBuf> {
+    let mut target = path.to_path_buf();
+
+    for _ in 0..MAX_LINKS {
+        match fs::symlink_metadata(&target) {
+            Ok(ref meta) if meta.file_type().is_symlink() => {
+                let link = fs::read_link(&target)?;
+
+                target = match target.parent() {
+                    Some(dir) => dir.join(link),
+                    None => link,
+                };
+            }
+            _ => return Ok(target),
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "too many levels of symbolic links",
+    ))
+}
+
+/// Creates a new temporary file next to `path`.\
+/// An existing file or link is never opened. Then another random name is tried.
+fn create_temp_file(path: &Path) -> io::Result<(PathBuf, File)> {
+    let mut oerr = None;
+
+    for _ in 0..TEMP_ATTEMPTS {
+        let temp = build_temp_path(path);
+
+        match OpenOptions::new().write(true).create_new(true).open(&temp) {
+            Ok(file) => return Ok((temp, file)),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::AlreadyExists {
+                    return Err(e);
+                }
+
+                oerr = Some(e);
+            }
+        }
+    }
+
+    Err(oerr.unwrap_or_else(|| io::Error::from(io::ErrorKind::AlreadyExists)))
+}
+
+fn write_temp_file(mut file: File, temp: &Path, path: &Path, data: &[u8]) -> io::Result<()> {
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    //A replaced File keeps its Permissions
+    if let Ok(meta) = fs::metadata(path) {
+        fs::set_permissions(temp, meta.permissions())?;
+    }
+
+    Ok(())
+}
+
+/// Writes `data` into the file `path`.\
+/// The data is written into a temporary file first which then replaces `path`.
+/// So `path` never contains incomplete data.
+/// When `path` is a symbolic link the file it points to is replaced and the link is kept.
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    let target = resolve_link_target(path)?;
+    let (temp, file) = create_temp_file(&target)?;
+    let result =
+        write_temp_file(file, &temp, &target, data).and_then(|_| fs::rename(&temp, &target));
+
+    if result.is_err() {
+        //The temporary File is not left behind
+        let _ = fs::remove_file(&temp);
+    }
+
+    result
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn files_write_atomic() {
+    let dir = std::env::temp_dir().join(format!("text-sanitizer_files_{}", process::id()));
+    let path = dir.join("output.txt");
+
+    fs::create_dir_all(&dir).expect("test directory not created");
+
+    write_atomic(&path, b"first").expect("file not written");
+    write_atomic(&path, b"second").expect("file not replaced");
+
+    assert_eq!(fs::read(&path).expect("file not readable"), b"second");
+    assert_eq!(build_backup_path(&path, ".bak"), dir.join("output.txt.bak"));
+    assert!(write_atomic(&dir.join("missing/output.txt"), b"data").is_err());
+    assert_eq!(
+        fs::read_dir(&dir)
+            .expect("test directory not readable")
+            .count(),
+        1
+    );
+
+    fs::remove_dir_all(&dir).expect("test directory not removed");
+}
+
+#[cfg(unix)]
+#[test]
+fn files_write_atomic_link() {
+    let dir = std::env::temp_dir().join(format!("text-sanitizer_links_{}", process::id()));
+    let path = dir.join("output.txt");
+    let link = dir.join("link.txt");
+
+    fs::create_dir_all(&dir).expect("test directory not created");
+    std::os::unix::fs::symlink("output.txt", &link).expect("link not created");
+
+    write_atomic(&link, b"first").expect("file not written");
+
+    assert!(fs::symlink_metadata(&link)
+        .expect("link not readable")
+        .file_type()
+        .is_symlink());
+    assert_eq!(fs::read(&path).expect("file not readable"), b"first");
+    assert_ne!(build_temp_path(&path), build_temp_path(&path));
+
+    fs::remove_dir_all(&dir).expect("test directory not removed");
+}
@@ -11,49 +11,324 @@
 * - The Rust module "app" must be installed
 */
 
+// The derives of the pinned serde version trigger lints of newer compilers
+#![allow(unknown_lints, non_local_definitions, unexpected_cfgs)]
+
 pub mod app;
+mod command;
+mod config;
+mod diff;
+mod files;
+#[cfg(target_os = "linux")]
+mod pty;
+mod serve;
+mod stream;
+mod strict;
+mod terminal;
 
-use app::RunTextSanitizer;
+use app::{RunTextSanitizer, EXIT_OK, EXIT_USAGE_ERROR};
+use config::load_config;
 
+use std::path::Path;
 use std::process::exit;
 
+//==============================================================================
+// Constants
+
+/// Usage text of the application shown with "--help"
+const USAGE: &str = "Usage: text-sanitizer [OPTIONS] [LANGUAGES] [FILES]
+       text-sanitizer [OPTIONS] [LANGUAGES] -- COMMAND [ARGUMENTS]
+       text-sanitizer [OPTIONS] [LANGUAGES] --listen ADDRESS | --socket FILE
+
+Converts the text of the FILES or of STDIN into plain ASCII text.
+Any argument of 2 or 3 letters like \"de\" is a language unless such a file exists.
+Any other argument is a file.
+The settings of \"$XDG_CONFIG_HOME/text-sanitizer/config.toml\" are applied first.
+The options override them. The languages, \"--keep\" and \"--columns\" replace
+the configured lists. Switches like \"--html\" are turned off with \"--no-html\".
+After \"--\" the COMMAND is run and its STDOUT and STDERR are sanitized.
+The application then exits with the exit code of the COMMAND.
+With \"--listen\" or \"--socket\" the application answers HTTP requests like
+\"POST /sanitize\" with {\"text\": \"...\", \"languages\": [\"de\"]} and \"GET /counters\".
+
+Options:
+  -h, --help                show this help and exit
+      --version             show the version and exit
+      --list-languages      list the available languages and exit
+      --config FILE         read the settings from the configuration FILE
+      --profile NAME        apply the profile NAME of the configuration file
+  -l, --lang LANG           apply the Language Replacement Map of LANG
+      --auto-lang           apply the languages of the locale and of LANGUAGE
+      --fallback MODE       handle unmapped characters with \"codepoint\", \"remove\" or \"replace\"
+      --replacement TEXT    replace unmapped characters with TEXT instead of \"?\"
+  -i, --import              read the text from STDIN line by line
+      --batch               read the whole text from STDIN before it is sanitized
+      --max-input-size SIZE limit the input in the batch mode to a SIZE like \"10M\"
+  -o, --output FILE         write the sanitized text into FILE
+      --in-place[=SUFFIX]   replace the FILES and keep a backup with SUFFIX
+  -k, --keep LIST           keep the characters of a list like \"U+20AC,b0,U+2010-U+2015\"
+  -w, --preserve-width      keep the display width of each line in the batch mode
+      --html                decode character references and keep the markup intact
+      --json                sanitize only the string values of a JSON document
+      --json-keys           sanitize also the keys of the JSON objects
+      --csv                 sanitize comma separated values field by field
+      --tsv                 sanitize tab separated values field by field
+      --columns LIST        sanitize only the columns of a list like \"2,4-6\"
+      --diff                show a report of the changed lines of plain text
+      --strict              fail when any character or invalid byte stays unmapped
+      --no-SWITCH           turn off a switch of the configuration like \"--no-strict\"
+      --merge               write the sanitized STDERR of the COMMAND to STDOUT
+      --label               start each line of the COMMAND with \"stdout: \" or \"stderr: \"
+      --pty                 run the COMMAND under a pseudo-terminal and pass on the keystrokes
+      --strip-escapes       remove the escape sequences of the COMMAND under a pseudo-terminal
+      --listen ADDRESS      serve HTTP requests on a localhost ADDRESS like \"127.0.0.1:8750\"
+      --socket FILE         serve HTTP requests on the Unix domain socket FILE
+  -v, --verbose             print notices
+  -d, --debug               print detailed activity messages
+  -p, --profiling           profile the processing activities
+
+Exit Codes:
+  0  the text was sanitized
+  1  reading or writing the data failed
+  2  invalid parameters
+  3  a character stays unmapped in the strict mode
+  4  an invalid byte stays unmapped in the strict mode
+  5  the data is not valid in the selected format like JSON
+  127  the COMMAND could not be started
+";
+
+//==============================================================================
+// Enumeration Command Declaration
+
+/// Describes what the application does after parsing the parameters.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run,
+    Help,
+    Version,
+    ListLanguages,
+}
+
 //==============================================================================
 // Auxiliary Functions
 
-fn parse_parameters(application: &mut RunTextSanitizer) {
-    //-------------------------------------
-    //Read the Script Parameters
+/// Takes the value of the option `soption` from the next parameter.
+fn take_value(vargs: &[String], iarg: &mut usize, soption: &str) -> Result<String, String> {
+    match vargs.get(*iarg) {
+        Some(svalue) => {
+            *iarg += 1;
 
-    let mut sarg;
-    let mut iargidx = 0;
+            Ok(svalue.clone())
+        }
+        None => Err(format!("option '{}' needs a value", soption)),
+    }
+}
 
-    //eprintln!("args: ");
+/// Checks whether a parameter looks like a language shortcode like "de" or "deu".\
+/// An existing file of that name stays a file.
+fn is_language_argument(argument: &str) -> bool {
+    (argument.len() == 2 || argument.len() == 3)
+        && argument.bytes().all(|uc| uc.is_ascii_alphabetic())
+        && !Path::new(argument).exists()
+}
 
-    // Prints each argument on a separate line
-    for argument in std::env::args() {
-        //eprintln!("[{}] '{}'", iargidx, argument.as_str());
+/// Sets the switch `sswitch` which can also be given in the configuration file.\
+/// Returns `false` when it is no such switch.
+fn set_switch(application: &mut RunTextSanitizer, sswitch: &str, bvalue: bool) -> bool {
+    match sswitch {
+        "auto-lang" => application.set_auto_lang(bvalue),
+        "preserve-width" => application.set_preserve_width(bvalue),
+        "html" => application.set_html(bvalue),
+        "json" => application.set_json(bvalue),
+        "json-keys" => application.set_json_keys(bvalue),
+        "csv" => application.set_csv(bvalue),
+        "tsv" if bvalue => application.set_csv_delimiter(b'\t'),
+        "tsv" => application.set_csv(false),
+        "diff" => application.set_diff(bvalue),
+        "strict" => application.set_strict(bvalue),
+        _ => return false,
+    } //match sswitch
 
-        if argument.starts_with("--") {
-            //Parameter with Double Dash
-            sarg = argument.split_at(2).1;
+    true
+}
 
-            match sarg.to_lowercase().as_str() {
-                "import" => application.set_import(true),
-                "verbose" => application.set_quiet(false),
-                "debug" => {
-                    //Reenable Notices
-                    application.set_quiet(false);
-                    //Enable Debug Output
-                    application.set_debug(true);
+/// Removes the configured languages before the first language of the parameters.
+fn replace_languages(application: &mut RunTextSanitizer, blanguages: &mut bool) {
+    if !*blanguages {
+        application.clear_request_languages();
+        *blanguages = true;
+    }
+}
+
+/// Finds the configuration file and the profile in the script parameters.\
+/// They are needed before the other parameters. So these override the configuration.
+fn find_config_parameters(vargs: &[String]) -> Result<(Option<String>, Option<String>), String> {
+    let mut oconfig = None;
+    let mut oprofile = None;
+    let mut iarg = 0;
+
+    while iarg < vargs.len() {
+        let argument = vargs[iarg].as_str();
+
+        iarg += 1;
+
+        if argument == "--" {
+            //The remaining Parameters are the wrapped Command
+            break;
+        }
+
+        for (soption, ovalue) in
+            [("--config", &mut oconfig), ("--profile", &mut oprofile)].iter_mut()
+        {
+            if argument == *soption {
+                **ovalue = Some(take_value(vargs, &mut iarg, argument)?);
+            } else if argument.starts_with(*soption)
+                && argument.as_bytes().get(soption.len()) == Some(&b'=')
+            {
+                **ovalue = Some(argument.split_at(soption.len() + 1).1.to_string());
+            }
+        } //for (soption, ovalue) in [("--config", &mut oconfig), ("--profile", &mut oprofile)]
+    } //while iarg < vargs.len()
+
+    Ok((oconfig, oprofile))
+}
+
+/// Parses the script parameters without the program name.\
+/// Unknown options and missing values are returned as error.
+/// Invalid values are reported by the application and set its error code.
+/// The first language, keep list and column list replace the configured ones.
+fn parse_parameters(
+    application: &mut RunTextSanitizer,
+    vargs: &[String],
+) -> Result<Command, String> {
+    let mut blanguages = false;
+    let mut bkeep = false;
+    let mut bcolumns = false;
+    let mut ofallback: Option<String> = None;
+    let mut oreplacement: Option<String> = None;
+    let mut iarg = 0;
+
+    while iarg < vargs.len() {
+        let argument = vargs[iarg].as_str();
+
+        iarg += 1;
+
+        if argument == "--" {
+            //The remaining Parameters are the wrapped Command
+            if iarg == vargs.len() {
+                return Err(String::from("option '--' needs a command"));
+            }
+
+            application.set_command(&vargs[iarg..]);
+
+            break;
+        } else if argument.starts_with("--") {
+            //Parameter with Double Dash and an optional Value after "="
+            let sarg = argument.split_at(2).1;
+            let (sname, ovalue) = match sarg.find('=') {
+                Some(ipos) => (&sarg[..ipos], Some(&sarg[(ipos + 1)..])),
+                None => (sarg, None),
+            };
+            let sname = sname.to_lowercase();
+
+            match sname.as_str() {
+                "lang" | "keep" | "columns" | "output" | "max-input-size" | "config"
+                | "profile" | "listen" | "socket" | "fallback" | "replacement" => {
+                    let svalue = match ovalue {
+                        Some(svalue) => svalue.to_string(),
+                        None => take_value(vargs, &mut iarg, argument)?,
+                    };
+
+                    match sname.as_str() {
+                        "lang" => {
+                            replace_languages(application, &mut blanguages);
+
+                            application.add_request_language(&svalue)
+                        }
+                        "keep" => {
+                            if !bkeep {
+                                application.clear_keep_list();
+                                bkeep = true;
+                            }
+
+                            application.add_keep_list(&svalue)
+                        }
+                        "columns" => {
+                            if !bcolumns {
+                                application.clear_column_list();
+                                bcolumns = true;
+                            }
+
+                            application.add_column_list(&svalue)
+                        }
+                        "fallback" => {
+                            ofallback = Some(svalue);
+
+                            EXIT_OK
+                        }
+                        "replacement" => {
+                            oreplacement = Some(svalue);
+
+                            EXIT_OK
+                        }
+                        "max-input-size" => application.set_max_input_size(&svalue),
+                        "listen" => {
+                            application.set_listen(&svalue);
+
+                            EXIT_OK
+                        }
+                        "socket" => {
+                            application.set_socket(&svalue);
+
+                            EXIT_OK
+                        }
+                        //The Configuration is applied before the Parameters
+                        "config" | "profile" => EXIT_OK,
+                        _ => application.set_output_file(&svalue),
+                    };
+                }
+                "in-place" => application.set_in_place(ovalue.unwrap_or("")),
+                _ => {
+                    if ovalue.is_some() {
+                        return Err(format!("option '--{}' does not take a value", sname));
+                    }
+
+                    match sname.as_str() {
+                        "help" => return Ok(Command::Help),
+                        "version" => return Ok(Command::Version),
+                        "list-languages" => return Ok(Command::ListLanguages),
+                        "import" => application.set_import(true),
+                        "verbose" => application.set_quiet(false),
+                        "debug" => {
+                            //Reenable Notices
+                            application.set_quiet(false);
+                            //Enable Debug Output
+                            application.set_debug(true);
+                        }
+                        "profiling" => application.set_profiling(true),
+                        "batch" => application.set_batch(true),
+                        "merge" => application.set_merge(true),
+                        "label" => application.set_label(true),
+                        "pty" => application.set_pty(true),
+                        "strip-escapes" => application.set_strip_escapes(true),
+                        _ => {
+                            //The Switches of the Configuration are turned off with "--no-"
+                            let (sswitch, bvalue) = match sname.find("no-") {
+                                Some(0) => (sname.split_at(3).1, false),
+                                _ => (sname.as_str(), true),
+                            };
+
+                            if !set_switch(application, sswitch, bvalue) {
+                                return Err(format!("unknown option '{}'", argument));
+                            }
+                        }
+                    } //match sname.as_str()
                 }
-                "profiling" => application.set_profiling(true),
-                _ => {}
-            } //match sarg
+            } //match sname.as_str()
         } else if argument.starts_with('-') {
             //Parameter with Single Dash
-            sarg = argument.split_at(1).1;
-
-            match sarg.to_lowercase().as_str() {
+            match argument.split_at(1).1.to_lowercase().as_str() {
+                "h" => return Ok(Command::Help),
                 "i" => application.set_import(true),
                 "v" => application.set_quiet(false),
                 "d" => {
@@ -63,19 +338,56 @@ fn parse_parameters(application: &mut RunTextSanitizer) {
                     application.set_debug(true);
                 }
                 "p" => application.set_profiling(true),
-                _ => {}
-            } //match sarg
-        } else if iargidx > 0 {
-            //Any parameter of 2 characters
-            if argument.len() == 2 {
-                application.add_request_language(&argument)
+                "w" => application.set_preserve_width(true),
+                "l" => {
+                    let svalue = take_value(vargs, &mut iarg, argument)?;
+
+                    replace_languages(application, &mut blanguages);
+                    application.add_request_language(&svalue);
+                }
+                "k" => {
+                    let svalue = take_value(vargs, &mut iarg, argument)?;
+
+                    if !bkeep {
+                        application.clear_keep_list();
+                        bkeep = true;
+                    }
+
+                    application.add_keep_list(&svalue);
+                }
+                "o" => {
+                    let svalue = take_value(vargs, &mut iarg, argument)?;
+
+                    application.set_output_file(&svalue);
+                }
+                _ => return Err(format!("unknown option '{}'", argument)),
+            } //match argument.split_at(1).1.to_lowercase().as_str()
+        } else if is_language_argument(argument) {
+            //Any parameter of 2 or 3 letters is a Language
+            if !application.has_language_map(argument) {
+                return Err(format!("unknown language '{}'", argument));
             }
+
+            replace_languages(application, &mut blanguages);
+            application.add_request_language(argument);
+        } else {
+            //Any other parameter is a File
+            application.add_input_file(argument);
         } //if argument.starts_with("--")
+    } //while iarg < vargs.len()
 
-        iargidx += 1;
-    } //for argument in std::env::args()
+    //A Replacement alone selects the "replace" Fallback
+    match (ofallback, oreplacement) {
+        (Some(smode), oreplacement) => {
+            application.set_fallback_mode(&smode, oreplacement.as_deref());
+        }
+        (None, Some(sreplacement)) => {
+            application.set_fallback_mode("replace", Some(&sreplacement));
+        }
+        (None, None) => {}
+    } //match (ofallback, oreplacement)
 
-    //eprintln!("args end.");
+    Ok(Command::Run)
 }
 
 fn run_app() -> i32 {
@@ -87,7 +399,54 @@ fn run_app() -> i32 {
     //Suppress Notices by default
     sanitizer.set_quiet(true);
 
-    parse_parameters(&mut sanitizer);
+    let vargs: Vec<String> = std::env::args().skip(1).collect();
+
+    let result = find_config_parameters(&vargs)
+        .and_then(|(oconfig, oprofile)| load_config(oconfig.as_deref(), oprofile.as_deref()));
+
+    match result {
+        Ok(Some(config)) => {
+            if sanitizer.apply_config(&config) != EXIT_OK {
+                return sanitizer.get_error_code();
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+
+            return EXIT_USAGE_ERROR;
+        }
+    } //match result
+
+    match parse_parameters(&mut sanitizer, &vargs) {
+        Ok(Command::Run) => {}
+        Ok(Command::Help) => {
+            print!("{}", USAGE);
+
+            return EXIT_OK;
+        }
+        Ok(Command::Version) => {
+            println!("text-sanitizer {}", env!("CARGO_PKG_VERSION"));
+
+            return EXIT_OK;
+        }
+        Ok(Command::ListLanguages) => {
+            for slanguage in sanitizer.get_languages() {
+                println!("{}", slanguage);
+            }
+
+            return EXIT_OK;
+        }
+        Err(e) => {
+            eprintln!("{}\nTry 'text-sanitizer --help' for more information.", e);
+
+            return EXIT_USAGE_ERROR;
+        }
+    } //match parse_parameters(&mut sanitizer, &vargs)
+
+    if sanitizer.get_error_code() != EXIT_OK {
+        return sanitizer.get_error_code();
+    }
 
     if sanitizer.is_debug() && !sanitizer.is_quiet() {
         eprintln!("app dmp 1:\n{:?}", sanitizer);
@@ -102,7 +461,7 @@ fn run_app() -> i32 {
     //Build the Report
 
     if !sanitizer.is_quiet() {
-        if ierr == 0 {
+        if ierr == EXIT_OK {
             eprintln!("Application finished with [{}]", ierr);
         } else {
             eprintln!("Application failed with [{}]", ierr);
@@ -125,3 +484,154 @@ pub fn main() {
         }
     }
 }
+
+//==============================================================================
+// Unit Tests
+
+#[cfg(test)]
+fn build_arguments(vargs: &[&str]) -> Vec<String> {
+    vargs.iter().map(|sarg| sarg.to_string()).collect()
+}
+
+#[test]
+fn parse_languages() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(
+        parse_parameters(
+            &mut app,
+            &build_arguments(&["de", "--lang=es", "-i", "--auto-lang"])
+        ),
+        Ok(Command::Run)
+    );
+    assert!(app.has_request_language("de"));
+    assert!(app.has_request_language("es"));
+    assert!(app.is_import());
+    assert!(app.is_auto_lang());
+    assert_eq!(app.get_error_code(), 0);
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["-l", "xx"])),
+        Ok(Command::Run)
+    );
+    assert!(!app.has_request_language("xx"));
+    assert_eq!(app.get_error_code(), EXIT_USAGE_ERROR);
+}
+
+#[test]
+fn parse_command() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(
+        parse_parameters(
+            &mut app,
+            &build_arguments(&["de", "--merge", "--", "ls", "-l", "de"])
+        ),
+        Ok(Command::Run)
+    );
+    assert!(app.has_request_language("de"));
+    assert_eq!(app.get_command(), &build_arguments(&["ls", "-l", "de"])[..]);
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["de", "--"])),
+        Err(String::from("option '--' needs a command"))
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["-x"])),
+        Err(String::from("unknown option '-x'"))
+    );
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["--html=yes"])),
+        Err(String::from("option '--html' does not take a value"))
+    );
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["de", "-l"])),
+        Err(String::from("option '-l' needs a value"))
+    );
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["deu"])),
+        Err(String::from("unknown language 'deu'"))
+    );
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["--version", "-x"])),
+        Ok(Command::Version)
+    );
+}
+
+#[test]
+fn parse_config() {
+    assert_eq!(
+        find_config_parameters(&build_arguments(&[
+            "de",
+            "--config",
+            "my.toml",
+            "--profile=html-de",
+            "--",
+            "--profile",
+            "x"
+        ])),
+        Ok((Some(String::from("my.toml")), Some(String::from("html-de"))))
+    );
+    assert_eq!(
+        find_config_parameters(&build_arguments(&["--configs", "--profile"])),
+        Err(String::from("option '--profile' needs a value"))
+    );
+
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["--profile", "html-de", "-i"])),
+        Ok(Command::Run)
+    );
+    assert!(app.is_import());
+}
+
+#[test]
+fn parse_overrides() {
+    let config = config::Config {
+        languages: Some(vec![String::from("de")]),
+        html: Some(true),
+        json_keys: Some(true),
+        strict: Some(true),
+        csv: Some(true),
+        ..config::Config::default()
+    };
+    let mut app = RunTextSanitizer::new_with_options(false, true, false, false);
+
+    assert_eq!(app.apply_config(&config), 0);
+    assert!(app.has_request_language("de"));
+
+    //The Parameters replace the configured Languages and turn off the Switches
+    assert_eq!(
+        parse_parameters(
+            &mut app,
+            &build_arguments(&["es", "--no-html", "--no-json", "--no-strict", "--no-tsv"])
+        ),
+        Ok(Command::Run)
+    );
+    assert!(app.has_request_language("es"));
+    assert!(app.has_request_language("en"));
+    assert!(!app.has_request_language("de"));
+    assert!(!app.is_html());
+    assert!(!app.is_json());
+    assert!(!app.is_strict());
+    assert!(!app.is_csv());
+    assert_eq!(app.get_error_code(), 0);
+
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["--no-help"])),
+        Err(String::from("unknown option '--no-help'"))
+    );
+    assert_eq!(
+        parse_parameters(&mut app, &build_arguments(&["--fallback", "drop"])),
+        Ok(Command::Run)
+    );
+    assert_eq!(app.get_error_code(), EXIT_USAGE_ERROR);
+}
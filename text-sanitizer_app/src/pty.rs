@@ -0,0 +1,417 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage pty.rs
+
+* This module implements the pseudo-terminal mode of the Text-Sanitizer application
+* It runs a program under a pseudo-terminal and sanitizes its output live
+* The keystrokes and the terminal size changes are passed on to the program
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+* - The Rust crate "libc" must be installed
+* - It is only available on Linux
+*/
+
+use std::ffi::CStr;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use text_sanitizer::TextSanitizer;
+
+use crate::app::{EXIT_COMMAND_NOT_RUN, EXIT_IO_ERROR};
+use crate::command::build_exit_code;
+use crate::terminal::TerminalSanitizer;
+
+//==============================================================================
+// Constants
+
+/// Size of the chunks which are read from the pseudo-terminal and the input
+const PTY_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Time in milliseconds after which a terminal size change is checked
+const PTY_POLL_TIMEOUT_MS: libc::c_int = 100;
+
+/// Set by the signal handler when the size of the terminal changed
+static WINDOW_CHANGED: AtomicBool = AtomicBool::new(false);
+
+//==============================================================================
+// Structure RawMode Declaration
+
+/// Puts the input terminal into the raw mode and restores it when dropped.\
+/// So each keystroke is passed on to the program at once.
+struct RawMode {
+    _ifd: RawFd,
+    _termios: libc::termios,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+extern "C" fn handle_window_change(_isignal: libc::c_int) {
+    WINDOW_CHANGED.store(true, Ordering::SeqCst);
+}
+
+/// Opens a new pseudo-terminal and returns its master and its slave side.
+fn open_pty() -> io::Result<(File, File)> {
+    let imaster = unsafe { libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY | libc::O_CLOEXEC) };
+
+    if imaster < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let master = unsafe { File::from_raw_fd(imaster) };
+    let mut vname = [0 as libc::c_char; 128];
+
+    unsafe {
+        if libc::grantpt(imaster) != 0
+            || libc::unlockpt(imaster) != 0
+            || libc::ptsname_r(imaster, vname.as_mut_ptr(), vname.len()) != 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    let sname = unsafe { CStr::from_ptr(vname.as_ptr()) }
+        .to_string_lossy()
+        .into_owned();
+    let slave = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(sname)?;
+
+    Ok((master, slave))
+}
+
+/// Copies the size of the user's terminal to the pseudo-terminal.\
+/// The program then receives the signal "SIGWINCH".
+fn copy_window_size(imaster: RawFd) {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+
+    for ifd in &[libc::STDOUT_FILENO, libc::STDIN_FILENO] {
+        if unsafe { libc::ioctl(*ifd, libc::TIOCGWINSZ, &mut size) } == 0 {
+            unsafe {
+                libc::ioctl(imaster, libc::TIOCSWINSZ, &size);
+            }
+
+            break;
+        }
+    } //for ifd in &[libc::STDOUT_FILENO, libc::STDIN_FILENO]
+}
+
+/// Passes the data of the input `ifd` on to the program.\
+/// `blineend` tells whether the data passed on so far ends with a line end.\
+/// Returns `false` when the input is closed.
+fn forward_input(
+    ifd: RawFd,
+    master: &mut File,
+    vbuffer: &mut [u8],
+    blineend: &mut bool,
+) -> io::Result<bool> {
+    let iread = unsafe {
+        libc::read(
+            ifd,
+            vbuffer.as_mut_ptr() as *mut libc::c_void,
+            vbuffer.len(),
+        )
+    };
+
+    if iread < 0 {
+        let e = io::Error::last_os_error();
+
+        return if e.kind() == io::ErrorKind::Interrupted {
+            Ok(true)
+        } else {
+            Err(e)
+        };
+    }
+
+    if iread == 0 {
+        return Ok(false);
+    }
+
+    master.write_all(&vbuffer[..iread as usize])?;
+
+    *blineend = vbuffer[iread as usize - 1] == b'\n';
+
+    Ok(true)
+}
+
+/// Tells the program that the input is closed like the user does on a terminal.\
+/// The terminal's end-of-file character is sent. It ends a started line first.
+fn send_end_of_input(master: &mut File, blineend: bool) -> io::Result<()> {
+    let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+    let mut ceof = 0x04;
+
+    if unsafe { libc::tcgetattr(master.as_raw_fd(), &mut termios) } == 0
+        && termios.c_cc[libc::VEOF] != 0
+    {
+        ceof = termios.c_cc[libc::VEOF];
+    }
+
+    if blineend {
+        master.write_all(&[ceof])
+    } else {
+        //The first Character only passes on the started Line
+        master.write_all(&[ceof, ceof])
+    }
+}
+
+/// Sanitizes the output of the pseudo-terminal until the program closes it.
+fn sanitize_pty<W: Write>(
+    terminal: &mut TerminalSanitizer,
+    master: &mut File,
+    oinput: Option<RawFd>,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut vbuffer = vec![0; PTY_CHUNK_SIZE];
+    let mut vfds = [
+        libc::pollfd {
+            fd: master.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        },
+        libc::pollfd {
+            fd: oinput.unwrap_or(-1),
+            events: libc::POLLIN,
+            revents: 0,
+        },
+    ];
+    let ievents = libc::POLLIN | libc::POLLHUP | libc::POLLERR;
+    let mut blineend = true;
+
+    loop {
+        if WINDOW_CHANGED.swap(false, Ordering::SeqCst) {
+            copy_window_size(master.as_raw_fd());
+        }
+
+        //A negative File Descriptor is ignored by poll()
+        let ipoll = unsafe { libc::poll(vfds.as_mut_ptr(), 2, PTY_POLL_TIMEOUT_MS) };
+
+        if ipoll < 0 {
+            let e = io::Error::last_os_error();
+
+            if e.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+
+            return Err(e);
+        }
+
+        if vfds[0].revents & ievents != 0 {
+            match master.read(&mut vbuffer) {
+                Ok(0) => break,
+                Ok(iread) => {
+                    out.write_all(terminal.feed(&vbuffer[..iread]).as_bytes())?;
+                    out.flush()?;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                //Linux reports the closed Slave Side as I/O Error
+                Err(ref e) if e.raw_os_error() == Some(libc::EIO) => break,
+                Err(e) => return Err(e),
+            } //match master.read(&mut vbuffer)
+        }
+
+        if vfds[1].fd >= 0
+            && vfds[1].revents & ievents != 0
+            && !forward_input(vfds[1].fd, master, &mut vbuffer, &mut blineend)?
+        {
+            //The Input is closed
+            send_end_of_input(master, blineend)?;
+            vfds[1].fd = -1;
+        }
+    } //loop
+
+    out.write_all(terminal.finish().as_bytes())?;
+    out.flush()
+}
+
+/// Runs the command `vcommand` with its arguments under a pseudo-terminal
+/// and sanitizes its output live.\
+/// The data of the input `oinput` is passed on to the command. The input is put into
+/// the raw mode when it is a terminal. Its end is passed on as the end-of-file character. With `bstrip` the escape sequences are removed.\
+/// Returns the exit code of the command. Errors are returned with their exit code.
+pub fn run_pty<W: Write>(
+    sanitizer: &TextSanitizer,
+    vcommand: &[String],
+    bstrip: bool,
+    oinput: Option<RawFd>,
+    out: &mut W,
+) -> Result<i32, (i32, String)> {
+    let (mut master, slave) = open_pty().map_err(|e| (EXIT_IO_ERROR, format!("pty: {}", e)))?;
+
+    copy_window_size(master.as_raw_fd());
+
+    let mut command = Command::new(&vcommand[0]);
+    let stdio = |file: &File| {
+        file.try_clone()
+            .map(Stdio::from)
+            .map_err(|e| (EXIT_IO_ERROR, format!("pty: {}", e)))
+    };
+
+    command
+        .args(&vcommand[1..])
+        .stdin(stdio(&slave)?)
+        .stdout(stdio(&slave)?)
+        .stderr(Stdio::from(slave));
+
+    unsafe {
+        //The Pseudo-Terminal becomes the controlling Terminal of the new Session
+        command.pre_exec(|| {
+            if libc::setsid() < 0 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(())
+        });
+    }
+
+    let mut child = command.spawn().map_err(|e| {
+        (
+            EXIT_COMMAND_NOT_RUN,
+            format!("command '{}': {}", vcommand[0], e),
+        )
+    })?;
+
+    //The Slave Side must be closed here to notice when the Command closes it
+    drop(command);
+
+    let result = {
+        let _oraw = oinput.and_then(RawMode::enable);
+        let ohandler = unsafe {
+            libc::signal(
+                libc::SIGWINCH,
+                handle_window_change as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            )
+        };
+        let mut terminal = TerminalSanitizer::new(sanitizer, bstrip);
+        let result = sanitize_pty(&mut terminal, &mut master, oinput, out);
+
+        unsafe {
+            libc::signal(libc::SIGWINCH, ohandler);
+        }
+
+        result
+    };
+
+    if let Err(e) = result {
+        let _ = child.kill();
+        let _ = child.wait();
+
+        return Err((EXIT_IO_ERROR, format!("pty: {}", e)));
+    }
+
+    child
+        .wait()
+        .map(build_exit_code)
+        .map_err(|e| (EXIT_IO_ERROR, format!("command '{}': {}", vcommand[0], e)))
+}
+
+//==============================================================================
+// Structure RawMode Implementation
+
+impl RawMode {
+    /// Puts the terminal `ifd` into the raw mode. Returns `None` when it is not a terminal.
+    fn enable(ifd: RawFd) -> Option<RawMode> {
+        let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+
+        if unsafe { libc::isatty(ifd) } != 1 || unsafe { libc::tcgetattr(ifd, &mut termios) } != 0 {
+            return None;
+        }
+
+        let mut raw = termios;
+
+        unsafe {
+            libc::cfmakeraw(&mut raw);
+            libc::tcsetattr(ifd, libc::TCSANOW, &raw);
+        }
+
+        Some(RawMode {
+            _ifd: ifd,
+            _termios: termios,
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self._ifd, libc::TCSANOW, &self._termios);
+        }
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn pty_output() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("en");
+    sanitizer.add_request_language("de");
+
+    let vcommand: Vec<String> = vec![
+        String::from("sh"),
+        String::from("-c"),
+        String::from("test -t 1 && printf '\\033[1mGr\\303\\274n\\033[0m\\n'; exit 4"),
+    ];
+    let mut voutput: Vec<u8> = Vec::new();
+
+    let icode = run_pty(&sanitizer, &vcommand, false, None, &mut voutput).expect("command not run");
+
+    //The Terminal converts the Newline into "\r\n"
+    assert_eq!(icode, 4);
+    assert_eq!(voutput, b"\x1b[1mGruen\x1b[0m\r\n");
+
+    let mut voutput: Vec<u8> = Vec::new();
+
+    run_pty(&sanitizer, &vcommand, true, None, &mut voutput).expect("command not run");
+
+    assert_eq!(voutput, b"Gruen\r\n");
+}
+
+#[test]
+fn pty_input_eof() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("en");
+    sanitizer.add_request_language("de");
+
+    let vcommand: Vec<String> = vec![String::from("cat")];
+    let mut vpipe = [-1 as libc::c_int; 2];
+
+    assert_eq!(unsafe { libc::pipe(vpipe.as_mut_ptr()) }, 0);
+
+    let input = unsafe { File::from_raw_fd(vpipe[0]) };
+    let mut writer = unsafe { File::from_raw_fd(vpipe[1]) };
+
+    //The last Line is not ended
+    writer
+        .write_all(b"Gr\xc3\xbcn\nBl")
+        .expect("input not written");
+    drop(writer);
+
+    let mut voutput: Vec<u8> = Vec::new();
+
+    let icode = run_pty(
+        &sanitizer,
+        &vcommand,
+        true,
+        Some(input.as_raw_fd()),
+        &mut voutput,
+    )
+    .expect("command not run");
+
+    assert_eq!(icode, 0);
+    //The Terminal echoes the Input before the Program reads it
+    assert_eq!(voutput, b"Gruen\r\nBlGruen\r\nBl");
+}
@@ -0,0 +1,1078 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage serve.rs
+
+* This module implements the serve mode of the Text-Sanitizer application
+* It keeps the compiled sanitizers in memory and answers HTTP requests
+* over a Unix domain socket or a localhost TCP port
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+* - The Rust crate "serde" must be installed
+* - The Rust crate "serde_json" must be installed
+*/
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_derive::{Deserialize, Serialize};
+
+use text_sanitizer::{CompiledSanitizer, CsvOptions, Fallback, TextSanitizer};
+
+use crate::app::{parse_keep_list, EXIT_IO_ERROR, EXIT_USAGE_ERROR};
+use crate::config::parse_fallback;
+
+//==============================================================================
+// Constants
+
+/// Maximum number of compiled sanitizers for the requested language lists
+const SERVE_CACHE_MAX: usize = 64;
+/// Maximum length of the request line and of each header line
+const SERVE_LINE_MAX: u64 = 8 * 1024;
+/// Maximum number of header lines of a request
+const SERVE_HEADERS_MAX: usize = 100;
+/// Maximum size of a request body when no input size is set
+const SERVE_BODY_MAX: u64 = 16 * 1024 * 1024;
+/// Time after which an idle connection is closed
+const SERVE_IDLE_TIMEOUT_SECS: u64 = 30;
+/// Number of worker threads which answer the connections of each listener
+const SERVE_WORKERS: usize = 16;
+/// Maximum number of accepted connections which wait for a free worker
+const SERVE_QUEUE_MAX: usize = 64;
+/// Pause after a failed accept. So a lack of file descriptors does not spin the listener
+const SERVE_ACCEPT_PAUSE_MILLIS: u64 = 100;
+
+//==============================================================================
+// Structure SanitizeRequest Declaration
+
+/// Body of a JSON request to "POST /sanitize".
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SanitizeRequest {
+    text: String,
+    #[serde(default)]
+    languages: Option<Vec<String>>,
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    fallback: Option<String>,
+    #[serde(default)]
+    replacement: Option<String>,
+    #[serde(default)]
+    keep: Option<String>,
+    #[serde(default)]
+    preserve_width: Option<bool>,
+    #[serde(default)]
+    stats: bool,
+}
+
+/// Options of a request which select its compiled sanitizer.\
+/// Options which are not given are taken from the application.
+#[derive(Debug, Default)]
+struct RequestOptions {
+    olangs: Option<Vec<String>>,
+    ofallback: Option<Fallback>,
+    vkeep: Vec<(char, char)>,
+    opreserve_width: Option<bool>,
+}
+
+/// Body of the JSON response to "POST /sanitize".
+#[derive(Debug, Serialize)]
+struct SanitizeResponse {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<SanitizeStats>,
+}
+
+/// Statistics of one sanitized text.
+#[derive(Debug, Serialize)]
+struct SanitizeStats {
+    input_bytes: usize,
+    output_bytes: usize,
+    lines: usize,
+    duration_us: u64,
+}
+
+/// Counters of the service returned by "GET /counters".
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ServiceCounters {
+    pub requests: u64,
+    pub errors: u64,
+    pub latency_us_total: u64,
+    pub latency_us_max: u64,
+    pub latency_us_avg: u64,
+}
+
+//==============================================================================
+// Structure HttpRequest Declaration
+
+/// The parts of an HTTP request which the service needs.
+#[derive(Debug, Default)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: String,
+    body: Vec<u8>,
+    bclose: bool,
+}
+
+/// The response to an HTTP request.
+#[derive(Debug)]
+struct HttpResponse {
+    istatus: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+//==============================================================================
+// Structure SanitizerService Declaration
+
+/// Sanitizes the texts of the requests with compiled sanitizers which are kept in memory.\
+/// It is shared between the connection threads.
+pub struct SanitizerService {
+    _sanitizer: TextSanitizer,
+    _odefault: Arc<CompiledSanitizer>,
+    _mcache: Mutex<HashMap<String, Arc<CompiledSanitizer>>>,
+    _imax_body: u64,
+    _irequests: AtomicU64,
+    _ierrors: AtomicU64,
+    _ilatency_total: AtomicU64,
+    _ilatency_max: AtomicU64,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Reads one line of at most `SERVE_LINE_MAX` bytes without its line end.\
+/// Returns `None` when the connection is closed before the line.
+fn read_http_line<R: BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut vline = Vec::new();
+
+    reader.take(SERVE_LINE_MAX).read_until(b'\n', &mut vline)?;
+
+    if vline.is_empty() {
+        return Ok(None);
+    }
+
+    if vline.last() != Some(&b'\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "line too long or incomplete",
+        ));
+    }
+
+    while vline.last() == Some(&b'\n') || vline.last() == Some(&b'\r') {
+        vline.pop();
+    }
+
+    String::from_utf8(vline)
+        .map(Some)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "line is not valid UTF-8"))
+}
+
+/// Reads the next request of the connection.\
+/// Returns `None` when the client closed the connection. Malformed requests are
+/// returned as error response.
+fn read_http_request<S: Read + Write>(
+    reader: &mut BufReader<S>,
+    imax_body: u64,
+) -> io::Result<Option<Result<HttpRequest, HttpResponse>>> {
+    let sline = match read_http_line(reader)? {
+        Some(sline) => sline,
+        None => return Ok(None),
+    };
+    let vparts: Vec<&str> = sline.split(' ').collect();
+
+    if vparts.len() != 3 || !vparts[2].starts_with("HTTP/1.") {
+        return Ok(Some(Err(build_error(400, "malformed request line"))));
+    }
+
+    let (spath, squery) = match vparts[1].find('?') {
+        Some(ipos) => (&vparts[1][..ipos], &vparts[1][(ipos + 1)..]),
+        None => (vparts[1], ""),
+    };
+    let mut request = HttpRequest {
+        method: vparts[0].to_string(),
+        path: spath.to_string(),
+        query: squery.to_string(),
+        //HTTP/1.0 closes the Connection after each Request
+        bclose: vparts[2] == "HTTP/1.0",
+        ..HttpRequest::default()
+    };
+    let mut ilength: u64 = 0;
+    let mut bcontinue = false;
+    let mut iheaders = 0;
+
+    loop {
+        let sheader = match read_http_line(reader)? {
+            Some(sheader) => sheader,
+            None => return Ok(None),
+        };
+
+        if sheader.is_empty() {
+            break;
+        }
+
+        iheaders += 1;
+
+        if iheaders > SERVE_HEADERS_MAX {
+            return Ok(Some(Err(build_error(431, "too many headers"))));
+        }
+
+        let ipos = match sheader.find(':') {
+            Some(ipos) => ipos,
+            None => return Ok(Some(Err(build_error(400, "malformed header")))),
+        };
+        let sname = sheader[..ipos].trim().to_lowercase();
+        let svalue = sheader[(ipos + 1)..].trim();
+
+        match sname.as_str() {
+            "content-length" => match svalue.parse::<u64>() {
+                Ok(ivalue) => ilength = ivalue,
+                Err(_) => return Ok(Some(Err(build_error(400, "invalid content length")))),
+            },
+            "connection" => {
+                let svalue = svalue.to_lowercase();
+
+                if svalue == "close" {
+                    request.bclose = true;
+                } else if svalue == "keep-alive" {
+                    request.bclose = false;
+                }
+            }
+            "expect" => bcontinue = svalue.eq_ignore_ascii_case("100-continue"),
+            "transfer-encoding" => {
+                return Ok(Some(Err(build_error(411, "content length required"))))
+            }
+            _ => {}
+        } //match sname.as_str()
+    } //loop
+
+    if ilength > imax_body {
+        //The Body is not read. So the Connection cannot be used further
+        return Ok(Some(Err(build_error(413, "request body too large"))));
+    }
+
+    if bcontinue && ilength > 0 {
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+        reader.get_mut().flush()?;
+    }
+
+    //The Body grows with the received Data and not with the announced Length
+    reader
+        .by_ref()
+        .take(ilength)
+        .read_to_end(&mut request.body)?;
+
+    if (request.body.len() as u64) < ilength {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "request body incomplete",
+        ));
+    }
+
+    Ok(Some(Ok(request)))
+}
+
+fn write_http_response<W: Write>(
+    writer: &mut W,
+    response: &HttpResponse,
+    bclose: bool,
+) -> io::Result<()> {
+    let sreason = match response.istatus {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    };
+    let shead = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}\r\n",
+        response.istatus,
+        sreason,
+        response.content_type,
+        response.body.len(),
+        if bclose { "Connection: close\r\n" } else { "" }
+    );
+
+    writer.write_all(shead.as_bytes())?;
+    writer.write_all(&response.body)?;
+    writer.flush()
+}
+
+fn build_json_response<T: serde::Serialize>(data: &T) -> HttpResponse {
+    match serde_json::to_vec(data) {
+        Ok(mut vbody) => {
+            vbody.push(b'\n');
+
+            HttpResponse {
+                istatus: 200,
+                content_type: "application/json",
+                body: vbody,
+            }
+        }
+        Err(e) => build_error(500, &e.to_string()),
+    }
+}
+
+fn build_error(istatus: u16, smessage: &str) -> HttpResponse {
+    let mut vbody = serde_json::json!({ "error": smessage })
+        .to_string()
+        .into_bytes();
+
+    vbody.push(b'\n');
+
+    HttpResponse {
+        istatus,
+        content_type: "application/json",
+        body: vbody,
+    }
+}
+
+/// Counts the lines of a text. A last line without line end is counted too.
+fn count_lines(text: &[u8]) -> usize {
+    let ilines = text.iter().filter(|uc| **uc == b'\n').count();
+
+    match text.last() {
+        Some(b'\n') | None => ilines,
+        Some(_) => ilines + 1,
+    }
+}
+
+/// Parses a switch of a query like "preserve_width" or "preserve_width=false".
+fn parse_switch(sname: &str, svalue: &str) -> Result<bool, String> {
+    match svalue {
+        "" | "1" | "true" => Ok(true),
+        "0" | "false" => Ok(false),
+        _ => Err(format!("parameter '{}': invalid value '{}'", sname, svalue)),
+    }
+}
+
+/// Decodes the percent-encoded bytes like "%E2%82%AC" of a query parameter.\
+/// A "+" stays a plus sign. So lists like "keep=U+20AC" can be passed as they are.\
+/// Invalid escapes and invalid UTF-8 are returned as error.
+fn decode_query_component(scomponent: &str) -> Result<String, String> {
+    let vbytes = scomponent.as_bytes();
+    let mut vdecoded: Vec<u8> = Vec::with_capacity(vbytes.len());
+    let mut ipos = 0;
+
+    while ipos < vbytes.len() {
+        if vbytes[ipos] == b'%' {
+            let ouc = scomponent
+                .get((ipos + 1)..(ipos + 3))
+                .filter(|shex| shex.bytes().all(|uc| uc.is_ascii_hexdigit()))
+                .and_then(|shex| u8::from_str_radix(shex, 16).ok());
+
+            match ouc {
+                Some(uc) => vdecoded.push(uc),
+                None => return Err(format!("invalid escape in '{}'", scomponent)),
+            }
+
+            ipos += 3;
+        } else {
+            vdecoded.push(vbytes[ipos]);
+            ipos += 1;
+        }
+    } //while ipos < vbytes.len()
+
+    String::from_utf8(vdecoded).map_err(|_| format!("invalid UTF-8 in '{}'", scomponent))
+}
+
+/// Builds the options of a request from the values of its JSON body or of its query.\
+/// Invalid values are returned as error.
+fn build_request_options(
+    olangs: Option<Vec<String>>,
+    ofallback: Option<&str>,
+    oreplacement: Option<&str>,
+    okeep: Option<&str>,
+    opreserve_width: Option<bool>,
+) -> Result<RequestOptions, String> {
+    //A Replacement alone selects the "replace" Fallback
+    let ofallback = match (ofallback, oreplacement) {
+        (Some(smode), oreplacement) => Some(parse_fallback(smode, oreplacement)?),
+        (None, Some(_)) => Some(parse_fallback("replace", oreplacement)?),
+        (None, None) => None,
+    };
+    let vkeep = match okeep {
+        Some(slist) => parse_keep_list(slist).map_err(|e| format!("keep list: {}", e))?,
+        None => Vec::new(),
+    };
+
+    Ok(RequestOptions {
+        olangs,
+        ofallback,
+        vkeep,
+        opreserve_width,
+    })
+}
+
+/// Answers the connections of one worker thread until the listener stops.\
+/// A failed connection is reported and does not stop the worker.
+fn run_worker<S: Read + Write>(service: &SanitizerService, receiver: &Mutex<Receiver<S>>) {
+    loop {
+        let ostream = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(e) => e.into_inner().recv(),
+        };
+        let stream = match ostream {
+            Ok(stream) => stream,
+            //The Listener has stopped
+            Err(_) => return,
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| service.handle_connection(stream))) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                if e.kind() != io::ErrorKind::WouldBlock
+                    && e.kind() != io::ErrorKind::TimedOut
+                    && e.kind() != io::ErrorKind::UnexpectedEof
+                {
+                    eprintln!("serve: {}", e);
+                }
+            }
+            Err(_) => eprintln!("serve: connection failed"),
+        } //match panic::catch_unwind(service.handle_connection(stream))
+    } //loop
+}
+
+/// Accepts the connections of the `listener` and hands them to a pool of `SERVE_WORKERS`
+/// threads. When all workers are busy further connections wait in a bounded queue.\
+/// Failed connections are reported and skipped. So the listener keeps running.
+fn accept_connections<L, S>(service: Arc<SanitizerService>, listener: L)
+where
+    L: Iterator<Item = io::Result<S>>,
+    S: Read + Write + Send + 'static,
+{
+    let (sender, receiver) = mpsc::sync_channel::<S>(SERVE_QUEUE_MAX);
+    let receiver = Arc::new(Mutex::new(receiver));
+    let mut vworkers = Vec::with_capacity(SERVE_WORKERS);
+
+    for _ in 0..SERVE_WORKERS {
+        let service = Arc::clone(&service);
+        let receiver = Arc::clone(&receiver);
+
+        vworkers.push(thread::spawn(move || run_worker(&service, &receiver)));
+    }
+
+    for stream in listener {
+        match stream {
+            Ok(stream) => {
+                if sender.send(stream).is_err() {
+                    eprintln!("serve: no worker left");
+
+                    break;
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                eprintln!("serve: {}", e);
+
+                thread::sleep(Duration::from_millis(SERVE_ACCEPT_PAUSE_MILLIS));
+            }
+        } //match stream
+    } //for stream in listener
+
+    //The Workers finish the waiting Connections
+    drop(sender);
+
+    for worker in vworkers {
+        let _ = worker.join();
+    }
+}
+
+/// Binds a TCP listener on the loopback address `saddress` like "127.0.0.1:8750".
+fn bind_tcp(saddress: &str) -> Result<TcpListener, (i32, String)> {
+    let vaddresses: Vec<_> = saddress
+        .to_socket_addrs()
+        .map_err(|e| (EXIT_USAGE_ERROR, format!("listen '{}': {}", saddress, e)))?
+        .collect();
+
+    if vaddresses.is_empty() || vaddresses.iter().any(|address| !address.ip().is_loopback()) {
+        return Err((
+            EXIT_USAGE_ERROR,
+            format!("listen '{}': only loopback addresses are allowed", saddress),
+        ));
+    }
+
+    TcpListener::bind(&vaddresses[..])
+        .map_err(|e| (EXIT_IO_ERROR, format!("listen '{}': {}", saddress, e)))
+}
+
+/// Binds a Unix domain socket at `path`. A stale socket file is replaced.
+#[cfg(unix)]
+fn bind_unix(path: &Path) -> Result<UnixListener, (i32, String)> {
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        //Only a left over Socket is removed and never a regular File
+        if metadata.file_type().is_socket() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    UnixListener::bind(path)
+        .map_err(|e| (EXIT_IO_ERROR, format!("socket '{}': {}", path.display(), e)))
+}
+
+/// Answers the requests on the localhost address `olisten` and on the Unix domain socket
+/// `osocket` until the application is stopped.\
+/// Errors are returned with their exit code.
+pub fn run_service(
+    service: SanitizerService,
+    olisten: Option<&str>,
+    osocket: Option<&Path>,
+) -> Result<(), (i32, String)> {
+    let service = Arc::new(service);
+    let mut vthreads = Vec::new();
+
+    if let Some(saddress) = olisten {
+        let listener = bind_tcp(saddress)?;
+        let service = Arc::clone(&service);
+
+        vthreads.push(thread::spawn(move || {
+            let connections = listener.incoming().map(|stream| {
+                stream.and_then(|stream| {
+                    stream.set_read_timeout(Some(Duration::from_secs(SERVE_IDLE_TIMEOUT_SECS)))?;
+
+                    Ok(stream)
+                })
+            });
+
+            accept_connections(service, connections)
+        }));
+    } //if let Some(saddress) = olisten
+
+    if let Some(path) = osocket {
+        #[cfg(unix)]
+        {
+            let listener = bind_unix(path)?;
+            let service = Arc::clone(&service);
+
+            vthreads.push(thread::spawn(move || {
+                let connections = listener.incoming().map(|stream| {
+                    stream.and_then(|stream| {
+                        stream
+                            .set_read_timeout(Some(Duration::from_secs(SERVE_IDLE_TIMEOUT_SECS)))?;
+
+                        Ok(stream)
+                    })
+                });
+
+                accept_connections(service, connections)
+            }));
+        }
+
+        #[cfg(not(unix))]
+        return Err((
+            EXIT_USAGE_ERROR,
+            format!("socket '{}': only available on Unix", path.display()),
+        ));
+    } //if let Some(path) = osocket
+
+    for handle in vthreads {
+        if handle.join().is_err() {
+            return Err((EXIT_IO_ERROR, String::from("serve: listener failed")));
+        }
+    } //for handle in vthreads
+
+    Ok(())
+}
+
+//==============================================================================
+// Structure SanitizerService Implementation
+
+impl SanitizerService {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Creates the service with the `LanguageMap`s and the options of the `sanitizer`.\
+    /// Its requested languages are used for requests without languages.
+    /// Request bodies are limited to `omax_body` bytes.
+    pub fn new(sanitizer: &TextSanitizer, omax_body: Option<u64>) -> SanitizerService {
+        SanitizerService {
+            _sanitizer: sanitizer.clone(),
+            _odefault: Arc::new(sanitizer.compile()),
+            _mcache: Mutex::new(HashMap::new()),
+            _imax_body: omax_body.unwrap_or(SERVE_BODY_MAX),
+            _irequests: AtomicU64::new(0),
+            _ierrors: AtomicU64::new(0),
+            _ilatency_total: AtomicU64::new(0),
+            _ilatency_max: AtomicU64::new(0),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Answers the requests of one connection until the client closes it.
+    pub fn handle_connection<S: Read + Write>(&self, stream: S) -> io::Result<()> {
+        let mut reader = BufReader::new(stream);
+
+        loop {
+            let (response, bclose) = match read_http_request(&mut reader, self._imax_body)? {
+                None => return Ok(()),
+                Some(Ok(request)) => (self.handle_request(&request), request.bclose),
+                Some(Err(response)) => {
+                    self._ierrors.fetch_add(1, Ordering::SeqCst);
+
+                    //The rest of the Request is not read. So the Connection is closed
+                    (response, true)
+                }
+            };
+
+            write_http_response(reader.get_mut(), &response, bclose)?;
+
+            if bclose {
+                return Ok(());
+            }
+        } //loop
+    }
+
+    fn handle_request(&self, request: &HttpRequest) -> HttpResponse {
+        let bsanitize = request.path == "/sanitize" || request.path == "/sanitize/raw";
+
+        if request.path == "/counters" {
+            return if request.method == "GET" {
+                build_json_response(&self.get_counters())
+            } else {
+                build_error(405, "method not allowed")
+            };
+        }
+
+        if !bsanitize {
+            return build_error(404, "not found");
+        }
+
+        let start = Instant::now();
+        let response = if request.method != "POST" {
+            build_error(405, "method not allowed")
+        } else if request.path == "/sanitize" {
+            self.sanitize_json_request(&request.body, start)
+        } else {
+            self.sanitize_raw_request(&request.query, &request.body)
+        };
+
+        self.count_request(start.elapsed(), response.istatus == 200);
+
+        response
+    }
+
+    /// Sanitizes the text of a JSON request like `{"text": "Grün", "languages": ["de"]}`.\
+    /// The options "fallback", "replacement", "keep" and "preserve_width" are optional.
+    fn sanitize_json_request(&self, vbody: &[u8], start: Instant) -> HttpResponse {
+        let request: SanitizeRequest = match serde_json::from_slice(vbody) {
+            Ok(request) => request,
+            Err(e) => return build_error(400, &format!("invalid request: {}", e)),
+        };
+        let text = request.text.as_bytes();
+        let omode = request.mode.as_deref();
+        let result = build_request_options(
+            request.languages,
+            request.fallback.as_deref(),
+            request.replacement.as_deref(),
+            request.keep.as_deref(),
+            request.preserve_width,
+        )
+        .and_then(|options| self.sanitize(text, &options, omode));
+        let ssanitized = match result {
+            Ok(ssanitized) => ssanitized,
+            Err(e) => return build_error(400, &e),
+        };
+        let ostats = if request.stats {
+            Some(SanitizeStats {
+                input_bytes: text.len(),
+                output_bytes: ssanitized.len(),
+                lines: count_lines(text),
+                duration_us: start.elapsed().as_micros() as u64,
+            })
+        } else {
+            None
+        };
+
+        build_json_response(&SanitizeResponse {
+            text: ssanitized,
+            stats: ostats,
+        })
+    }
+
+    /// Sanitizes the raw bytes of a request with the query like "lang=de,es&mode=html".\
+    /// The parameters "fallback", "replacement", "keep" and "preserve_width" are optional.
+    /// Their names and values are percent-decoded.
+    fn sanitize_raw_request(&self, squery: &str, vbody: &[u8]) -> HttpResponse {
+        let mut olangs: Option<Vec<String>> = None;
+        let mut omode: Option<String> = None;
+        let mut ofallback: Option<String> = None;
+        let mut oreplacement: Option<String> = None;
+        let mut okeep: Option<String> = None;
+        let mut opreserve_width: Option<bool> = None;
+
+        for sparameter in squery
+            .split('&')
+            .filter(|sparameter| !sparameter.is_empty())
+        {
+            let (sname, svalue) = match sparameter.find('=') {
+                Some(ipos) => (&sparameter[..ipos], &sparameter[(ipos + 1)..]),
+                None => (sparameter, ""),
+            };
+            let (sname, svalue) = match decode_query_component(sname)
+                .and_then(|sname| decode_query_component(svalue).map(|svalue| (sname, svalue)))
+            {
+                Ok(parameter) => parameter,
+                Err(e) => return build_error(400, &format!("query: {}", e)),
+            };
+
+            match sname.as_str() {
+                "lang" => olangs
+                    .get_or_insert_with(Vec::new)
+                    .extend(svalue.split(',').map(|slang| slang.to_string())),
+                "mode" => omode = Some(svalue),
+                "fallback" => ofallback = Some(svalue),
+                "replacement" => oreplacement = Some(svalue),
+                "keep" => okeep = Some(svalue),
+                "preserve_width" => match parse_switch(&sname, &svalue) {
+                    Ok(bpreserve) => opreserve_width = Some(bpreserve),
+                    Err(e) => return build_error(400, &e),
+                },
+                _ => return build_error(400, &format!("unknown parameter '{}'", sname)),
+            }
+        } //for sparameter in squery.split('&')
+
+        let result = build_request_options(
+            olangs,
+            ofallback.as_deref(),
+            oreplacement.as_deref(),
+            okeep.as_deref(),
+            opreserve_width,
+        )
+        .and_then(|options| self.sanitize(vbody, &options, omode.as_deref()));
+
+        match result {
+            Ok(ssanitized) => HttpResponse {
+                istatus: 200,
+                content_type: "text/plain; charset=utf-8",
+                body: ssanitized.into_bytes(),
+            },
+            Err(e) => build_error(400, &e),
+        }
+    }
+
+    /// Sanitizes the `text` with the request `options` in the mode `omode`.\
+    /// The modes are "plain", "html", "json", "json-keys", "csv" and "tsv".
+    fn sanitize(
+        &self,
+        text: &[u8],
+        options: &RequestOptions,
+        omode: Option<&str>,
+    ) -> Result<String, String> {
+        let sanitizer = self.find_sanitizer(options)?;
+
+        match omode.unwrap_or("plain") {
+            "plain" => Ok(sanitizer.sanitize_u8(text)),
+            "html" => Ok(sanitizer.sanitize_html_u8(text)),
+            "json" => sanitizer
+                .sanitize_json_u8(text, false)
+                .map_err(|e| format!("invalid JSON: {}", e)),
+            "json-keys" => sanitizer
+                .sanitize_json_u8(text, true)
+                .map_err(|e| format!("invalid JSON: {}", e)),
+            "csv" => Ok(sanitizer.sanitize_csv_u8(text, &CsvOptions::csv())),
+            "tsv" => Ok(sanitizer.sanitize_csv_u8(text, &CsvOptions::tsv())),
+            smode => Err(format!("unknown mode '{}'", smode)),
+        }
+    }
+
+    /// Returns the compiled sanitizer for the request `options` with the languages
+    /// in their priority order.\
+    /// It is compiled on the first request and kept for the next requests.
+    /// Requests without options use the sanitizer of the application.
+    fn find_sanitizer(&self, options: &RequestOptions) -> Result<Arc<CompiledSanitizer>, String> {
+        if options.olangs.is_none()
+            && options.ofallback.is_none()
+            && options.vkeep.is_empty()
+            && options.opreserve_width.is_none()
+        {
+            return Ok(Arc::clone(&self._odefault));
+        }
+
+        let skey = format!(
+            "{:?}|{:?}|{:?}|{:?}",
+            options.olangs, options.ofallback, options.vkeep, options.opreserve_width
+        );
+
+        if let Some(sanitizer) = self.lock_cache().get(&skey) {
+            return Ok(Arc::clone(sanitizer));
+        }
+
+        //The Sanitizer is compiled without holding the Lock
+        let mut sanitizer = self._sanitizer.clone();
+
+        if let Some(vlangs) = &options.olangs {
+            sanitizer.clear_request_languages();
+
+            for slang in vlangs {
+                if !sanitizer.has_language_map(slang) {
+                    return Err(format!("language '{}': no language map", slang));
+                }
+
+                sanitizer.add_request_language(slang);
+            }
+        } //if let Some(vlangs) = &options.olangs
+
+        if let Some(fallback) = &options.ofallback {
+            sanitizer.set_fallback(fallback.clone());
+        }
+
+        for (first, last) in options.vkeep.iter() {
+            sanitizer.add_keep_range(*first, *last);
+        }
+
+        if let Some(bpreserve) = options.opreserve_width {
+            sanitizer.set_preserve_width(bpreserve);
+        }
+
+        let compiled = Arc::new(sanitizer.compile());
+        let mut mcache = self.lock_cache();
+
+        if mcache.len() >= SERVE_CACHE_MAX {
+            mcache.clear();
+        }
+
+        //Another Thread might have compiled the same Sanitizer meanwhile
+        Ok(Arc::clone(mcache.entry(skey).or_insert(compiled)))
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, HashMap<String, Arc<CompiledSanitizer>>> {
+        match self._mcache.lock() {
+            Ok(mcache) => mcache,
+            //A panicked Thread leaves the Cache intact
+            Err(e) => e.into_inner(),
+        }
+    }
+
+    fn count_request(&self, duration: Duration, bsuccess: bool) {
+        let ilatency = duration.as_micros() as u64;
+
+        self._irequests.fetch_add(1, Ordering::SeqCst);
+        self._ilatency_total.fetch_add(ilatency, Ordering::SeqCst);
+
+        let mut imax = self._ilatency_max.load(Ordering::SeqCst);
+
+        while ilatency > imax {
+            match self._ilatency_max.compare_exchange(
+                imax,
+                ilatency,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => break,
+                Err(icurrent) => imax = icurrent,
+            }
+        } //while ilatency > imax
+
+        if !bsuccess {
+            self._ierrors.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Returns the number of sanitize requests, the failed requests and their latency
+    /// in microseconds.
+    pub fn get_counters(&self) -> ServiceCounters {
+        let irequests = self._irequests.load(Ordering::SeqCst);
+        let ilatency_total = self._ilatency_total.load(Ordering::SeqCst);
+
+        ServiceCounters {
+            requests: irequests,
+            errors: self._ierrors.load(Ordering::SeqCst),
+            latency_us_total: ilatency_total,
+            latency_us_max: self._ilatency_max.load(Ordering::SeqCst),
+            latency_us_avg: ilatency_total.checked_div(irequests).unwrap_or(0),
+        }
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[cfg(test)]
+struct TestStream {
+    input: io::Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Read for TestStream {
+    fn read(&mut self, vbuffer: &mut [u8]) -> io::Result<usize> {
+        self.input.read(vbuffer)
+    }
+}
+
+#[cfg(test)]
+impl Write for TestStream {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.output.write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn serve_requests() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("de");
+
+    let service = SanitizerService::new(&sanitizer, Some(1024));
+    let sjson = "{\"text\": \"Espa\u{f1}a Gr\u{fc}n\", \"languages\": [\"es\", \"de\"]}";
+    let mut vinput = format!(
+        "POST /sanitize HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+        sjson.len(),
+        sjson
+    )
+    .into_bytes();
+
+    vinput.extend_from_slice(
+        b"POST /sanitize/raw?mode=html HTTP/1.1\r\nContent-Length: 14\r\n\r\nGr\xfcn &uuml; \xc3\xbc",
+    );
+    vinput.extend_from_slice(
+        b"POST /sanitize/raw?lang=es,en&fallback=replace&replacement=_&keep=fc HTTP/1.1\r\nContent-Length: 9\r\n\r\n\xc3\xb1\xc3\xbc\xc3\xa4\xe2\x82\xac",
+    );
+    vinput.extend_from_slice(
+        b"POST /sanitize/raw?replacement=%3F&keep=%E2%82%AC HTTP/1.1\r\nContent-Length: 8\r\n\r\n\xe2\x82\xac\xc3\xbc\xe2\x9c\x93",
+    );
+    vinput.extend_from_slice(b"POST /sanitize?x HTTP/1.1\r\nContent-Length: 2\r\n\r\n{}");
+    vinput.extend_from_slice(b"GET /counters HTTP/1.1\r\nConnection: close\r\n\r\n");
+
+    let mut stream = TestStream {
+        input: io::Cursor::new(vinput),
+        output: Vec::new(),
+    };
+
+    service
+        .handle_connection(&mut stream)
+        .expect("connection failed");
+
+    let soutput = String::from_utf8(stream.output).expect("response not valid UTF-8");
+    let vresponses: Vec<&str> = soutput.split("HTTP/1.1 ").skip(1).collect();
+
+    assert_eq!(vresponses.len(), 6);
+    assert!(vresponses[0].starts_with("200 OK\r\n"));
+    assert!(vresponses[0].ends_with("\r\n\r\n{\"text\":\"Espana Gruen\"}\n"));
+    assert!(vresponses[1].ends_with("\r\n\r\nGruen ue ue"));
+    assert!(vresponses[2].ends_with("\r\n\r\nn\u{fc}_EUR"));
+    //The Parameters are percent-decoded
+    assert!(vresponses[3].ends_with("\r\n\r\n\u{20ac}ue?"));
+    assert!(vresponses[4].starts_with("400 Bad Request\r\n"));
+    assert!(vresponses[5].contains("Connection: close\r\n"));
+    assert!(vresponses[5].contains("{\"requests\":5,\"errors\":1,"));
+
+    let counters = service.get_counters();
+
+    assert_eq!(counters.requests, 5);
+    assert_eq!(counters.errors, 1);
+    assert_eq!(count_lines(b"a\nb"), 2);
+    assert_eq!(count_lines(b"a\n"), 1);
+    assert_eq!(
+        decode_query_component("U+20AC,%C2%B0"),
+        Ok(String::from("U+20AC,\u{b0}"))
+    );
+    assert!(decode_query_component("%E2%82").is_err());
+    assert!(decode_query_component("%G0").is_err());
+    assert!(decode_query_component("%+1").is_err());
+
+    //The Options select their own compiled Sanitizer
+    let options = build_request_options(None, Some("remove"), None, None, Some(true))
+        .expect("options not valid");
+
+    assert_eq!(
+        service.sanitize("\u{f1}a b".as_bytes(), &options, None),
+        Ok(String::from("a  b"))
+    );
+    assert!(!Arc::ptr_eq(
+        &service
+            .find_sanitizer(&options)
+            .expect("sanitizer not compiled"),
+        &service._odefault
+    ));
+    assert_eq!(
+        build_request_options(None, Some("drop"), None, None, None).map(|_| ()),
+        Err(String::from("unknown fallback 'drop'"))
+    );
+}
+
+#[cfg(test)]
+#[derive(Clone)]
+struct SharedStream {
+    input: Arc<Mutex<io::Cursor<Vec<u8>>>>,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+#[cfg(test)]
+impl Read for SharedStream {
+    fn read(&mut self, vbuffer: &mut [u8]) -> io::Result<usize> {
+        self.input.lock().expect("input locked").read(vbuffer)
+    }
+}
+
+#[cfg(test)]
+impl Write for SharedStream {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.output.lock().expect("output locked").write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn serve_accept_errors() {
+    let service = Arc::new(SanitizerService::new(&TextSanitizer::new(), None));
+    let stream = SharedStream {
+        input: Arc::new(Mutex::new(io::Cursor::new(
+            b"POST /sanitize/raw HTTP/1.0\r\nContent-Length: 2\r\n\r\nok".to_vec(),
+        ))),
+        output: Arc::new(Mutex::new(Vec::new())),
+    };
+    let vconnections = vec![
+        Err(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            "connection aborted",
+        )),
+        Ok(stream.clone()),
+    ];
+
+    //The failed Connection does not stop the Listener
+    accept_connections(Arc::clone(&service), vconnections.into_iter());
+
+    let voutput = stream.output.lock().expect("output locked").clone();
+
+    assert!(voutput.starts_with(b"HTTP/1.1 200 OK\r\n"));
+    assert!(voutput.ends_with(b"\r\n\r\nok"));
+    assert_eq!(service.get_counters().requests, 1);
+}
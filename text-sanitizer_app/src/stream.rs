@@ -0,0 +1,364 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage stream.rs
+
+* This module implements the streaming mode of the Text-Sanitizer application
+* The input is read in chunks and each complete line is written at once
+* Incomplete lines are written when no more input arrives within the timeout
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+*/
+
+use std::io::{self, Read, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use text_sanitizer::TextSanitizer;
+
+use crate::app::{EXIT_INVALID_ENCODING, EXIT_IO_ERROR, EXIT_UNMAPPED};
+use crate::strict::{check_strict, StrictViolation};
+
+//==============================================================================
+// Constants
+
+/// Size of the chunks which are read from the input
+const STREAM_CHUNK_SIZE: usize = 8 * 1024;
+/// Maximum length of a pending line before it is written without its line end
+const STREAM_LINE_MAX: usize = 64 * 1024;
+/// Number of read chunks which wait for the output
+const STREAM_QUEUE_SIZE: usize = 4;
+
+//==============================================================================
+// Structure StreamSanitizer Declaration
+
+/// Sanitizes the input line by line as it arrives.\
+/// Only the incomplete last line is kept in memory.
+pub struct StreamSanitizer<'a> {
+    _sanitizer: &'a TextSanitizer,
+    _bstrict: bool,
+    _iline: usize,
+    _vpending: Vec<u8>,
+    _olabel: Option<String>,
+    _bline_start: bool,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Finds the end of the data without a trailing incomplete UTF-8 sequence.\
+/// So a character is never split between two writes.
+pub(crate) fn find_safe_end(data: &[u8]) -> usize {
+    for ilen in 1..=std::cmp::min(3, data.len()) {
+        let uc = data[data.len() - ilen];
+
+        if uc >= 0xc0 {
+            //Lead Byte of a Sequence
+            let iseqlen = if uc >= 0xf0 {
+                4
+            } else if uc >= 0xe0 {
+                3
+            } else {
+                2
+            };
+
+            if iseqlen > ilen {
+                return data.len() - ilen;
+            }
+
+            break;
+        } else if uc < 0x80 {
+            break;
+        }
+    } //for ilen in 1..=std::cmp::min(3, data.len())
+
+    data.len()
+}
+
+/// Reads the input in chunks on its own thread and sanitizes it with the `streamer`.\
+/// Pending data is written when no input arrives within the `timeout`.
+/// Errors are returned with their exit code.
+pub fn sanitize_stream<R, W>(
+    streamer: &mut StreamSanitizer,
+    mut reader: R,
+    writer: &mut W,
+    timeout: Duration,
+) -> Result<(), (i32, String)>
+where
+    R: Read + Send + 'static,
+    W: Write,
+{
+    //The bounded Queue keeps the Memory Usage flat when the Output is slow
+    let (sender, receiver) = mpsc::sync_channel::<io::Result<Vec<u8>>>(STREAM_QUEUE_SIZE);
+
+    thread::spawn(move || loop {
+        let mut vchunk = vec![0; STREAM_CHUNK_SIZE];
+
+        match reader.read(&mut vchunk) {
+            Ok(iread) => {
+                vchunk.truncate(iread);
+
+                if sender.send(Ok(vchunk)).is_err() || iread == 0 {
+                    break;
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                let _ = sender.send(Err(e));
+
+                break;
+            }
+        } //match reader.read(&mut vchunk)
+    });
+
+    loop {
+        match receiver.recv_timeout(timeout) {
+            Ok(Ok(vchunk)) => {
+                if vchunk.is_empty() {
+                    //End of the Input
+                    return streamer.finish(writer);
+                }
+
+                streamer.feed(&vchunk, writer)?;
+            }
+            Ok(Err(e)) => return Err((EXIT_IO_ERROR, format!("input: {}", e))),
+            Err(RecvTimeoutError::Timeout) => streamer.flush_pending(writer)?,
+            Err(RecvTimeoutError::Disconnected) => return streamer.finish(writer),
+        } //match receiver.recv_timeout(timeout)
+    } //loop
+}
+
+//==============================================================================
+// Structure StreamSanitizer Implementation
+
+impl<'a> StreamSanitizer<'a> {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    /// Creates a `StreamSanitizer` which checks each line in the strict mode
+    /// before it is written when `bstrict` is set.
+    pub fn new(sanitizer: &'a TextSanitizer, bstrict: bool) -> StreamSanitizer<'a> {
+        StreamSanitizer {
+            _sanitizer: sanitizer,
+            _bstrict: bstrict,
+            _iline: 1,
+            _vpending: Vec::new(),
+            _olabel: None,
+            _bline_start: true,
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Sets a label like "stderr: " which is written at the start of each line.
+    pub fn set_label(&mut self, slabel: &str) {
+        self._olabel = Some(slabel.to_string());
+    }
+
+    /// Adds a chunk of input and writes all complete lines.
+    pub fn feed<W: Write + ?Sized>(
+        &mut self,
+        data: &[u8],
+        writer: &mut W,
+    ) -> Result<(), (i32, String)> {
+        self._vpending.extend_from_slice(data);
+
+        match self._vpending.iter().rposition(|uc| *uc == b'\n') {
+            Some(ipos) => self.write_pending(ipos + 1, writer),
+            None => {
+                if self._vpending.len() >= STREAM_LINE_MAX {
+                    //Overlong Lines do not grow the Buffer further
+                    let iend = find_safe_end(&self._vpending);
+
+                    self.write_pending(iend, writer)
+                } else {
+                    Ok(())
+                }
+            }
+        } //match self._vpending.iter().rposition(|uc| *uc == b'\n')
+    }
+
+    /// Writes the incomplete line without a trailing incomplete character.\
+    /// With the preserved display width the line is kept until its line end
+    /// because its width is balanced over the whole line.
+    pub fn flush_pending<W: Write + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<(), (i32, String)> {
+        if self._sanitizer.is_preserve_width() {
+            return Ok(());
+        }
+
+        let iend = find_safe_end(&self._vpending);
+
+        self.write_pending(iend, writer)
+    }
+
+    /// Ends the line which was written without its line end.\
+    /// So the next data starts a new line with its label.
+    pub fn end_line<W: Write + ?Sized>(&mut self, writer: &mut W) -> Result<(), (i32, String)> {
+        if self._bline_start {
+            return Ok(());
+        }
+
+        self._bline_start = true;
+
+        writer
+            .write_all(b"\n")
+            .and_then(|_| writer.flush())
+            .map_err(|e| (EXIT_IO_ERROR, format!("output: {}", e)))
+    }
+
+    /// Writes all the remaining data at the end of the input.
+    pub fn finish<W: Write + ?Sized>(&mut self, writer: &mut W) -> Result<(), (i32, String)> {
+        let iend = self._vpending.len();
+
+        self.write_pending(iend, writer)
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self._vpending.is_empty()
+    }
+
+    fn write_pending<W: Write + ?Sized>(
+        &mut self,
+        iend: usize,
+        writer: &mut W,
+    ) -> Result<(), (i32, String)> {
+        if iend == 0 {
+            return Ok(());
+        }
+
+        let vpart: Vec<u8> = self._vpending.drain(..iend).collect();
+
+        if self._bstrict {
+            //The Line Numbers count from the Start of the Input
+            check_strict(self._sanitizer, &vpart).map_err(|e| match e {
+                StrictViolation::Unmapped(c, iline) => {
+                    let e = StrictViolation::Unmapped(c, iline + self._iline - 1);
+
+                    (EXIT_UNMAPPED, format!("strict: {}", e))
+                }
+                StrictViolation::InvalidByte(uc, iline) => {
+                    let e = StrictViolation::InvalidByte(uc, iline + self._iline - 1);
+
+                    (EXIT_INVALID_ENCODING, format!("strict: {}", e))
+                }
+            })?;
+        }
+
+        self._iline += vpart.iter().filter(|uc| **uc == b'\n').count();
+
+        let ssanitized = self.add_labels(self._sanitizer.sanitize_u8(&vpart));
+
+        writer
+            .write_all(ssanitized.as_bytes())
+            .and_then(|_| writer.flush())
+            .map_err(|e| (EXIT_IO_ERROR, format!("output: {}", e)))
+    }
+
+    fn add_labels(&mut self, ssanitized: String) -> String {
+        let slabel = match &self._olabel {
+            Some(slabel) => slabel,
+            None => return ssanitized,
+        };
+        let mut slabeled = String::with_capacity(ssanitized.len() + slabel.len());
+
+        for c in ssanitized.chars() {
+            if self._bline_start {
+                slabeled.push_str(slabel);
+                self._bline_start = false;
+            }
+
+            slabeled.push(c);
+
+            if c == '\n' {
+                self._bline_start = true;
+            }
+        } //for c in ssanitized.chars()
+
+        slabeled
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn stream_lines() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("de");
+
+    let mut streamer = StreamSanitizer::new(&sanitizer, false);
+    let mut voutput: Vec<u8> = Vec::new();
+
+    streamer
+        .feed(b"Gr\xc3\xbcn\nRot \xc3", &mut voutput)
+        .expect("chunk not written");
+
+    assert_eq!(voutput, b"Gruen\n");
+
+    //The incomplete Character is kept until the next Chunk
+    streamer
+        .flush_pending(&mut voutput)
+        .expect("pending data not written");
+
+    assert_eq!(voutput, b"Gruen\nRot ");
+
+    streamer
+        .feed(b"\xbc\n\xc3", &mut voutput)
+        .expect("chunk not written");
+    streamer.finish(&mut voutput).expect("rest not written");
+
+    assert_eq!(voutput, b"Gruen\nRot ue\n(?c3)");
+
+    sanitizer.set_preserve_width(true);
+
+    let mut streamer = StreamSanitizer::new(&sanitizer, false);
+    let mut voutput: Vec<u8> = Vec::new();
+
+    //The incomplete Line waits for its Line End to keep its Width
+    streamer
+        .feed("\u{f1}a ".as_bytes(), &mut voutput)
+        .expect("chunk not written");
+    streamer
+        .flush_pending(&mut voutput)
+        .expect("pending data not written");
+
+    assert!(voutput.is_empty());
+
+    streamer
+        .feed(b"b|\n", &mut voutput)
+        .expect("chunk not written");
+
+    assert_eq!(
+        voutput,
+        sanitizer.sanitize_u8("\u{f1}a b|\n".as_bytes()).as_bytes()
+    );
+
+    let mut streamer = StreamSanitizer::new(&sanitizer, true);
+    let mut voutput: Vec<u8> = Vec::new();
+
+    assert_eq!(
+        sanitize_stream(
+            &mut streamer,
+            io::Cursor::new(b"a\nb\n\xc3\xb1\n".to_vec()),
+            &mut voutput,
+            Duration::from_millis(100)
+        ),
+        Err((
+            EXIT_UNMAPPED,
+            String::from("strict: unmapped character U+00F1 '\u{f1}' in line 3")
+        ))
+    );
+    //The failing Chunk is not written
+    assert!(voutput.is_empty());
+}
@@ -0,0 +1,103 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage strict.rs
+
+* This module implements the strict mode of the Text-Sanitizer application
+* It finds the first character without a mapping or the first invalid byte
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+*/
+
+use std::fmt;
+
+use text_sanitizer::TextSanitizer;
+
+//==============================================================================
+// Enumeration StrictViolation Declaration
+
+/// Describes the content which fails the strict mode.
+#[derive(Debug, PartialEq)]
+pub enum StrictViolation {
+    /// A character without a mapping in the requested languages at the given line.
+    Unmapped(char, usize),
+    /// A byte which is not valid UTF-8 at the given line.
+    InvalidByte(u8, usize),
+}
+
+impl fmt::Display for StrictViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StrictViolation::Unmapped(c, iline) => {
+                if c.is_control() {
+                    write!(
+                        f,
+                        "unmapped character U+{:04X} in line {}",
+                        *c as u32, iline
+                    )
+                } else {
+                    write!(
+                        f,
+                        "unmapped character U+{:04X} '{}' in line {}",
+                        *c as u32, c, iline
+                    )
+                }
+            }
+            StrictViolation::InvalidByte(uc, iline) => {
+                write!(f, "invalid byte {:#04x} in line {}", uc, iline)
+            }
+        }
+    }
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Finds the first character of `text` which has no mapping in the requested languages
+/// of the `sanitizer` or the first invalid byte which is not rescued by a mapping.
+pub fn check_strict(sanitizer: &TextSanitizer, text: &[u8]) -> Result<(), StrictViolation> {
+    match sanitizer.find_unmapped(text) {
+        Some((ipos, ochr)) => {
+            let iline = text[..ipos].iter().filter(|uc| **uc == b'\n').count() + 1;
+
+            match ochr {
+                Some(c) => Err(StrictViolation::Unmapped(c, iline)),
+                None => Err(StrictViolation::InvalidByte(text[ipos], iline)),
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn strict_violations() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("en");
+    sanitizer.add_request_language("de");
+
+    assert_eq!(
+        check_strict(&sanitizer, b"Gr\xc3\xbcn \xe2\x9d\xa4\r\n"),
+        Ok(())
+    );
+    assert_eq!(
+        check_strict(&sanitizer, b"Gr\xc3\xbcn\n\xc3\xb1"),
+        Err(StrictViolation::Unmapped('\u{f1}', 2))
+    );
+    assert_eq!(
+        check_strict(&sanitizer, b"\n\nGr\xfan"),
+        Err(StrictViolation::InvalidByte(0xfa, 3))
+    );
+    //Invalid Bytes can be rescued by a Mapping
+    assert_eq!(check_strict(&sanitizer, b"Gr\xfcn 5\x80"), Ok(()));
+    assert_eq!(
+        StrictViolation::Unmapped('\u{7}', 1).to_string(),
+        "unmapped character U+0007 in line 1"
+    );
+}
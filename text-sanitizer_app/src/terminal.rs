@@ -0,0 +1,274 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_app
+* @subpackage terminal.rs
+
+* This module implements the sanitizing of terminal output
+* Escape sequences and the control characters which move the cursor are kept intact
+* or stripped as a whole. They are never split between two writes
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+*/
+
+use text_sanitizer::TextSanitizer;
+
+use crate::stream::find_safe_end;
+
+//==============================================================================
+// Constants
+
+/// Escape Character which starts an escape sequence
+const ESCAPE: u8 = 0x1b;
+
+/// Maximum length of an unterminated escape sequence before it is given up
+const ESCAPE_LENGTH_MAX: usize = 4096;
+
+//==============================================================================
+// Structure TerminalSanitizer Declaration
+
+/// Sanitizes the output of a terminal program as it arrives.\
+/// The text between the escape sequences is sanitized. Escape sequences and
+/// the control characters carriage return, backspace and bell are kept intact.
+/// With `bstrip` the escape sequences are removed as a whole.
+pub struct TerminalSanitizer<'a> {
+    _sanitizer: &'a TextSanitizer,
+    _bstrip: bool,
+    _vpending: Vec<u8>,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+/// Checks whether a control character moves the cursor or rings the bell in a terminal.
+fn is_terminal_control(uc: u8) -> bool {
+    uc == b'\r' || uc == 0x07 || uc == 0x08
+}
+
+/// Finds the end of the string of an OSC, DCS, SOS, PM or APC sequence.\
+/// It is terminated by the String Terminator "ESC \" or the bell for OSC.
+fn find_string_end(data: &[u8], start: usize, bbell: bool) -> Option<usize> {
+    let mut ipos = start;
+
+    while ipos < data.len() {
+        if bbell && data[ipos] == 0x07 {
+            return Some(ipos + 1);
+        }
+
+        if data[ipos] == ESCAPE {
+            return match data.get(ipos + 1) {
+                Some(b'\\') => Some(ipos + 2),
+                //The String is cut off by another Sequence
+                Some(_) => Some(ipos),
+                None => None,
+            };
+        }
+
+        ipos += 1;
+    } //while ipos < data.len()
+
+    None
+}
+
+/// Finds the end of the escape sequence starting at `start` following ECMA-48.\
+/// Returns `None` when the sequence is not complete yet.
+pub fn find_escape_end(data: &[u8], start: usize) -> Option<usize> {
+    let mut ipos = start + 1;
+
+    match data.get(ipos) {
+        None => None,
+        Some(b'[') => {
+            //Control Sequence with Parameter and Intermediate Bytes and a Final Byte
+            ipos += 1;
+
+            while ipos < data.len() {
+                match data[ipos] {
+                    0x20..=0x3f => ipos += 1,
+                    0x40..=0x7e => return Some(ipos + 1),
+                    //A malformed Sequence ends before the unexpected Byte
+                    _ => return Some(ipos),
+                }
+            }
+
+            None
+        }
+        Some(b']') => find_string_end(data, ipos + 1, true),
+        Some(b'P') | Some(b'X') | Some(b'^') | Some(b'_') => find_string_end(data, ipos + 1, false),
+        Some(_) => {
+            //Intermediate Bytes and a Final Byte like "ESC ( B"
+            while ipos < data.len() {
+                match data[ipos] {
+                    0x20..=0x2f => ipos += 1,
+                    0x30..=0x7e => return Some(ipos + 1),
+                    _ => return Some(ipos),
+                }
+            }
+
+            None
+        }
+    } //match data.get(ipos)
+}
+
+//==============================================================================
+// Structure TerminalSanitizer Implementation
+
+impl<'a> TerminalSanitizer<'a> {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    pub fn new(sanitizer: &'a TextSanitizer, bstrip: bool) -> TerminalSanitizer<'a> {
+        TerminalSanitizer {
+            _sanitizer: sanitizer,
+            _bstrip: bstrip,
+            _vpending: Vec::new(),
+        }
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Adds a chunk of output and returns the sanitized output which can be written.\
+    /// Incomplete characters and escape sequences are kept until the next chunk.
+    pub fn feed(&mut self, data: &[u8]) -> String {
+        let mut vbuffer = std::mem::take(&mut self._vpending);
+        let mut srsout = String::with_capacity(vbuffer.len() + data.len());
+        let mut itext = 0;
+        let mut ic = 0;
+
+        vbuffer.extend_from_slice(data);
+
+        while ic < vbuffer.len() {
+            let uc = vbuffer[ic];
+
+            if uc == ESCAPE {
+                match find_escape_end(&vbuffer, ic) {
+                    Some(iend) => {
+                        self.push_text(&vbuffer[itext..ic], &mut srsout);
+                        self.push_escape(&vbuffer[ic..iend], &mut srsout);
+
+                        ic = iend;
+                        itext = ic;
+                    }
+                    None if vbuffer.len() - ic > ESCAPE_LENGTH_MAX => {
+                        //An overlong unterminated Sequence is given up and sanitized as Text
+                        ic += 1;
+                    }
+                    None => {
+                        //The Sequence is completed by the next Chunk
+                        break;
+                    }
+                } //match find_escape_end(&vbuffer, ic)
+            } else if is_terminal_control(uc) {
+                self.push_text(&vbuffer[itext..ic], &mut srsout);
+                srsout.push(uc as char);
+
+                ic += 1;
+                itext = ic;
+            } else {
+                ic += 1;
+            }
+        } //while ic < vbuffer.len()
+
+        //Incomplete Characters at the End are completed by the next Chunk
+        let iend = if ic < vbuffer.len() {
+            ic
+        } else {
+            itext + find_safe_end(&vbuffer[itext..])
+        };
+
+        self.push_text(&vbuffer[itext..iend], &mut srsout);
+        self._vpending = vbuffer.split_off(iend);
+
+        srsout
+    }
+
+    /// Returns the sanitized output of the remaining data at the end of the output.\
+    /// An unterminated escape sequence at the end is sanitized as text.
+    pub fn finish(&mut self) -> String {
+        let vbuffer = std::mem::take(&mut self._vpending);
+        let mut srsout = String::new();
+
+        self.push_text(&vbuffer, &mut srsout);
+
+        srsout
+    }
+
+    fn push_text(&self, text: &[u8], srsout: &mut String) {
+        if !text.is_empty() {
+            srsout.push_str(&self._sanitizer.sanitize_u8(text));
+        }
+    }
+
+    fn push_escape(&self, sequence: &[u8], srsout: &mut String) {
+        if self._bstrip {
+            return;
+        }
+
+        match sequence.get(1) {
+            Some(b']') | Some(b'P') | Some(b'X') | Some(b'^') | Some(b'_') => {
+                //The String like a Window Title is sanitized between the Introducer
+                //and the Terminator
+                let iend = if sequence.ends_with(b"\x1b\\") {
+                    sequence.len() - 2
+                } else if sequence[1] == b']' && sequence.ends_with(b"\x07") {
+                    sequence.len() - 1
+                } else {
+                    sequence.len()
+                };
+
+                srsout.push_str(&String::from_utf8_lossy(&sequence[..2]));
+                self.push_text(&sequence[2..iend], srsout);
+                srsout.push_str(&String::from_utf8_lossy(&sequence[iend..]));
+            }
+            _ => srsout.push_str(&String::from_utf8_lossy(sequence)),
+        } //match sequence.get(1)
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn terminal_escapes() {
+    let mut sanitizer = TextSanitizer::new();
+
+    sanitizer.add_request_language("en");
+    sanitizer.add_request_language("de");
+
+    assert_eq!(find_escape_end(b"\x1b[1;31mrot", 0), Some(7));
+    assert_eq!(find_escape_end(b"\x1b[1;3", 0), None);
+    assert_eq!(find_escape_end(b"\x1b]0;Titel\x07", 0), Some(10));
+    assert_eq!(find_escape_end(b"\x1b]0;Titel\x1b\\", 0), Some(11));
+    assert_eq!(find_escape_end(b"\x1b(B", 0), Some(3));
+
+    let mut terminal = TerminalSanitizer::new(&sanitizer, false);
+
+    //The Sequences and the Characters are split between the Chunks
+    assert_eq!(terminal.feed(b"\x1b[1;3"), "");
+    assert_eq!(terminal.feed(b"1mGr\xc3"), "\x1b[1;31mGr");
+    assert_eq!(terminal.feed(b"\xbcn\x1b[0m\r\n"), "uen\x1b[0m\r\n");
+    assert_eq!(terminal.feed(b"\xe2\x94\x9c\x08"), "|-\x08");
+    assert_eq!(terminal.finish(), "");
+
+    //The Strings of the Sequences are sanitized as well
+    assert_eq!(
+        terminal.feed(b"\x1b]0;T\xc3\xbcr\x07\x1b_Gr\xc3\xbcn\x1b\\"),
+        "\x1b]0;Tuer\x07\x1b_Gruen\x1b\\"
+    );
+
+    //An unterminated Sequence at the End is sanitized as Text
+    assert_eq!(terminal.feed(b"ok\x1b]0;T\xc3\xbcr"), "ok");
+    assert_eq!(terminal.finish(), "]0;Tuer");
+
+    let mut terminal = TerminalSanitizer::new(&sanitizer, true);
+
+    assert_eq!(
+        terminal.feed(b"\x1b]0;T\xc3\xbcr\x07\x1b[1mT\xc3\xbcr\x1b[0m\r\n\x1b["),
+        "Tuer\r\n"
+    );
+    assert_eq!(terminal.finish(), "[");
+}
@@ -0,0 +1,77 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_ffi
+* @subpackage include/text_sanitizer.h
+
+* This header declares the C interface to the Text-Sanitizer library
+* The declarations must match the functions in "src/ffi.rs"
+* which is checked by the test "tests/c_interface.rs"
+*
+*---------------------------------
+* Requirements:
+* - link against "libtext_sanitizer_ffi"
+*/
+
+#ifndef TEXT_SANITIZER_H
+#define TEXT_SANITIZER_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Error Codes */
+#define TEXT_SANITIZER_OK 0
+#define TEXT_SANITIZER_ERROR_NULL -1
+#define TEXT_SANITIZER_ERROR_ENCODING -2
+#define TEXT_SANITIZER_ERROR_MAP -3
+#define TEXT_SANITIZER_ERROR_OUTPUT -4
+#define TEXT_SANITIZER_ERROR_INTERNAL -5
+
+/* Opaque Handle of a TextSanitizer */
+typedef struct TextSanitizer TextSanitizer;
+
+/* Creates a TextSanitizer with the built-in Language Maps "en", "de" and "es".
+ * The handle must be released with text_sanitizer_free(). */
+TextSanitizer *text_sanitizer_new(void);
+
+/* Creates a TextSanitizer with runtime options like "-q" or "-d".
+ * Returns NULL if the options are not valid UTF-8. */
+TextSanitizer *text_sanitizer_new_with_options(const char *options);
+
+/* Releases a TextSanitizer handle. NULL is ignored. */
+void text_sanitizer_free(TextSanitizer *sanitizer);
+
+/* Adds a language shortcode. The order of the calls is the priority order. */
+int text_sanitizer_add_request_language(TextSanitizer *sanitizer, const char *language);
+
+/* Removes all requested languages. */
+int text_sanitizer_clear_request_languages(TextSanitizer *sanitizer);
+
+/* Adds or replaces the Language Map of one language from a JSON object
+ * like {"e4": "ae", "fc": "ue"} */
+int text_sanitizer_load_language_map(TextSanitizer *sanitizer, const char *language,
+                                     const uint8_t *map, size_t map_len);
+
+/* Adds or replaces several Language Maps from a JSON object
+ * like {"fi": {"e4": "a"}, "de": {"fc": "ue"}} */
+int text_sanitizer_load_conversion_map(TextSanitizer *sanitizer, const uint8_t *map,
+                                       size_t map_len);
+
+/* Sanitizes a buffer of raw text bytes.
+ * On success *output holds a NUL terminated string of *output_len bytes
+ * which must be released with text_sanitizer_free_output(). */
+int text_sanitizer_sanitize(const TextSanitizer *sanitizer, const uint8_t *text,
+                            size_t text_len, char **output, size_t *output_len);
+
+/* Releases an output string of text_sanitizer_sanitize(). NULL is ignored. */
+void text_sanitizer_free_output(char *output);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* TEXT_SANITIZER_H */
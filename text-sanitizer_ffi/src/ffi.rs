@@ -0,0 +1,350 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_ffi
+* @subpackage ffi.rs
+
+* This module implements the C interface to the Text-Sanitizer library
+* The declarations in "include/text_sanitizer.h" must match these functions
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+* - The Rust crate "serde_json" must be installed
+*/
+
+extern crate serde_json;
+extern crate text_sanitizer;
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+use text_sanitizer::{ConversionMap, LanguageMap, TextSanitizer};
+
+//==============================================================================
+// Constants
+
+/// The operation was successful
+pub const TEXT_SANITIZER_OK: c_int = 0;
+/// A required pointer argument was `NULL`
+pub const TEXT_SANITIZER_ERROR_NULL: c_int = -1;
+/// A string argument was not valid UTF-8
+pub const TEXT_SANITIZER_ERROR_ENCODING: c_int = -2;
+/// The map buffer could not be parsed as JSON map
+pub const TEXT_SANITIZER_ERROR_MAP: c_int = -3;
+/// The sanitized output contains a NUL byte and cannot be returned as C string
+pub const TEXT_SANITIZER_ERROR_OUTPUT: c_int = -4;
+/// The library failed unexpectedly
+pub const TEXT_SANITIZER_ERROR_INTERNAL: c_int = -5;
+
+//==============================================================================
+// Auxiliary Functions
+
+unsafe fn parse_c_str<'a>(text: *const c_char) -> Result<&'a str, c_int> {
+    if text.is_null() {
+        return Err(TEXT_SANITIZER_ERROR_NULL);
+    }
+
+    CStr::from_ptr(text)
+        .to_str()
+        .map_err(|_| TEXT_SANITIZER_ERROR_ENCODING)
+}
+
+unsafe fn parse_buffer<'a>(buffer: *const u8, buffer_len: usize) -> Result<&'a [u8], c_int> {
+    if buffer_len == 0 {
+        Ok(&[])
+    } else if buffer.is_null() {
+        Err(TEXT_SANITIZER_ERROR_NULL)
+    } else {
+        Ok(slice::from_raw_parts(buffer, buffer_len))
+    }
+}
+
+fn into_error_code(result: Result<(), c_int>) -> c_int {
+    match result {
+        Ok(_) => TEXT_SANITIZER_OK,
+        Err(ierr) => ierr,
+    }
+}
+
+//==============================================================================
+// C Interface
+
+/// Creates a new `TextSanitizer` with the built-in `LanguageMap`s.\
+/// No language is requested yet.
+/// The returned handle must be released with `text_sanitizer_free()`.
+#[no_mangle]
+pub extern "C" fn text_sanitizer_new() -> *mut TextSanitizer {
+    Box::into_raw(Box::new(TextSanitizer::new()))
+}
+
+/// Creates a new `TextSanitizer` with runtime options like "-q" or "-d".\
+/// The returned handle must be released with `text_sanitizer_free()`.
+/// It returns `NULL` if `options` is not a valid UTF-8 string.
+///
+/// # Safety
+///
+/// `options` must be `NULL` or point to a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_new_with_options(
+    options: *const c_char,
+) -> *mut TextSanitizer {
+    let soptions = if options.is_null() {
+        ""
+    } else {
+        match parse_c_str(options) {
+            Ok(s) => s,
+            Err(_) => return ptr::null_mut(),
+        }
+    };
+
+    Box::into_raw(Box::new(TextSanitizer::new_with_options_string(soptions)))
+}
+
+/// Releases a `TextSanitizer` handle. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `sanitizer` must be `NULL` or a handle created by this library
+/// which was not released before.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_free(sanitizer: *mut TextSanitizer) {
+    if !sanitizer.is_null() {
+        drop(Box::from_raw(sanitizer));
+    }
+}
+
+/// Adds a language shortcode to the applied `LanguageMap`s.
+/// The order of the calls is the priority order of the languages.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle and `language` must point to
+/// a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_add_request_language(
+    sanitizer: *mut TextSanitizer,
+    language: *const c_char,
+) -> c_int {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    into_error_code(parse_c_str(language).map(|slang| {
+        (*sanitizer).add_request_language(slang);
+    }))
+}
+
+/// Removes all requested languages.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_clear_request_languages(
+    sanitizer: *mut TextSanitizer,
+) -> c_int {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    (*sanitizer).clear_request_languages();
+
+    TEXT_SANITIZER_OK
+}
+
+/// Adds or replaces the `LanguageMap` of one language.\
+/// The buffer holds a JSON object of the replacements by codepoint like
+/// `{"e4": "ae", "fc": "ue"}`.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle, `language` must point to a NUL terminated
+/// string and `map` must point to at least `map_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_load_language_map(
+    sanitizer: *mut TextSanitizer,
+    language: *const c_char,
+    map: *const u8,
+    map_len: usize,
+) -> c_int {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    let slang = match parse_c_str(language) {
+        Ok(s) => s,
+        Err(ierr) => return ierr,
+    };
+    let vmap = match parse_buffer(map, map_len) {
+        Ok(m) => m,
+        Err(ierr) => return ierr,
+    };
+
+    match serde_json::from_slice::<LanguageMap>(vmap) {
+        Ok(lngmap) => {
+            (*sanitizer).set_language_map(slang, lngmap);
+
+            TEXT_SANITIZER_OK
+        }
+        Err(_) => TEXT_SANITIZER_ERROR_MAP,
+    }
+}
+
+/// Adds or replaces several `LanguageMap`s at once.\
+/// The buffer holds a JSON object of the `LanguageMap`s by language shortcode like
+/// `{"fi": {"e4": "a"}, "de": {"fc": "ue"}}`.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle and `map` must point to at least `map_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_load_conversion_map(
+    sanitizer: *mut TextSanitizer,
+    map: *const u8,
+    map_len: usize,
+) -> c_int {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    let vmap = match parse_buffer(map, map_len) {
+        Ok(m) => m,
+        Err(ierr) => return ierr,
+    };
+
+    match serde_json::from_slice::<ConversionMap>(vmap) {
+        Ok(conv_map) => {
+            for (slang, lngmap) in conv_map.0 {
+                (*sanitizer).set_language_map(&slang, lngmap);
+            }
+
+            TEXT_SANITIZER_OK
+        }
+        Err(_) => TEXT_SANITIZER_ERROR_MAP,
+    }
+}
+
+/// Sanitizes a buffer of raw text bytes.\
+/// On success `*output` points to a new NUL terminated string and `*output_len`
+/// holds its length without the NUL byte. The caller must release the output
+/// with `text_sanitizer_free_output()`.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle, `text` must point to at least `text_len` bytes
+/// and `output` must be a valid pointer. `output_len` may be `NULL`.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_sanitize(
+    sanitizer: *const TextSanitizer,
+    text: *const u8,
+    text_len: usize,
+    output: *mut *mut c_char,
+    output_len: *mut usize,
+) -> c_int {
+    if sanitizer.is_null() || output.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    *output = ptr::null_mut();
+
+    let vtext = match parse_buffer(text, text_len) {
+        Ok(t) => t,
+        Err(ierr) => return ierr,
+    };
+
+    let srsout = match catch_unwind(AssertUnwindSafe(|| (*sanitizer).sanitize_u8(vtext))) {
+        Ok(s) => s,
+        Err(_) => return TEXT_SANITIZER_ERROR_INTERNAL,
+    };
+
+    let irslen = srsout.len();
+
+    match CString::new(srsout) {
+        Ok(cs) => {
+            *output = cs.into_raw();
+
+            if !output_len.is_null() {
+                *output_len = irslen;
+            }
+
+            TEXT_SANITIZER_OK
+        }
+        Err(_) => TEXT_SANITIZER_ERROR_OUTPUT,
+    }
+}
+
+/// Releases an output string of `text_sanitizer_sanitize()`. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `output` must be `NULL` or a string returned by `text_sanitizer_sanitize()`
+/// which was not released before.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_free_output(output: *mut c_char) {
+    if !output.is_null() {
+        drop(CString::from_raw(output));
+    }
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn ffi_sanitize_buffer() {
+    let vsparkle_heart = [240, 159, 146, 150, 119, 250];
+    let sfinnish_map = b"{\"e4\": \"a\"}";
+    let mut poutput: *mut c_char = ptr::null_mut();
+    let mut ioutlen: usize = 0;
+
+    unsafe {
+        let sanitizer = text_sanitizer_new();
+
+        assert_eq!(
+            text_sanitizer_add_request_language(sanitizer, b"en\0".as_ptr() as *const c_char),
+            TEXT_SANITIZER_OK
+        );
+        assert_eq!(
+            text_sanitizer_load_language_map(
+                sanitizer,
+                b"fi\0".as_ptr() as *const c_char,
+                sfinnish_map.as_ptr(),
+                sfinnish_map.len()
+            ),
+            TEXT_SANITIZER_OK
+        );
+        assert_eq!(
+            text_sanitizer_load_language_map(
+                sanitizer,
+                b"xx\0".as_ptr() as *const c_char,
+                b"[1]".as_ptr(),
+                3
+            ),
+            TEXT_SANITIZER_ERROR_MAP
+        );
+        assert_eq!(
+            text_sanitizer_sanitize(
+                sanitizer,
+                vsparkle_heart.as_ptr(),
+                vsparkle_heart.len(),
+                &mut poutput,
+                &mut ioutlen
+            ),
+            TEXT_SANITIZER_OK
+        );
+
+        assert_eq!(CStr::from_ptr(poutput).to_str(), Ok("<3w(?fa)"));
+        assert_eq!(ioutlen, 8);
+
+        text_sanitizer_free_output(poutput);
+        text_sanitizer_free(sanitizer);
+
+        assert_eq!(
+            text_sanitizer_add_request_language(ptr::null_mut(), ptr::null()),
+            TEXT_SANITIZER_ERROR_NULL
+        );
+    }
+}
@@ -0,0 +1,25 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_ffi
+* @subpackage lib.rs
+
+* This module provides the C interface library for the Text-Sanitizer
+*
+*---------------------------------
+* Requirements:
+* - The Rust module "ffi" must be installed
+*/
+
+//! C interface to the Text-Sanitizer library
+//!
+//! The crate builds a `cdylib` and a `staticlib` which can be linked into C programs.
+//! The functions are declared in the header `include/text_sanitizer.h`.
+//!
+//! A `TextSanitizer` handle is created with `text_sanitizer_new()` and released with
+//! `text_sanitizer_free()`. The sanitized output of `text_sanitizer_sanitize()` is
+//! released with `text_sanitizer_free_output()`.
+
+pub mod ffi;
+
+pub use ffi::*;
@@ -0,0 +1,97 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_ffi
+* @subpackage tests/c/test_text_sanitizer.c
+
+* This program tests the C interface of the Text-Sanitizer library
+* It is compiled and run by the test "tests/c_interface.rs"
+*
+*---------------------------------
+* Requirements:
+* - The header "text_sanitizer.h" must be in the include path
+* - link against "libtext_sanitizer_ffi"
+*/
+
+#include <stdio.h>
+#include <string.h>
+
+#include "text_sanitizer.h"
+
+static int ierrors = 0;
+
+static void check_code(const char *name, int code, int expected) {
+  if (code != expected) {
+    fprintf(stderr, "%s: code '%d' != '%d'\n", name, code, expected);
+    ierrors++;
+  }
+}
+
+static void check_sanitize(const TextSanitizer *sanitizer, const char *name,
+                           const uint8_t *text, size_t text_len, const char *expected) {
+  char *output = NULL;
+  size_t output_len = 0;
+
+  check_code(name, text_sanitizer_sanitize(sanitizer, text, text_len, &output, &output_len),
+             TEXT_SANITIZER_OK);
+
+  if (output == NULL) {
+    fprintf(stderr, "%s: no output\n", name);
+    ierrors++;
+  } else {
+    if (strcmp(output, expected) != 0 || output_len != strlen(expected)) {
+      fprintf(stderr, "%s: output '%s' != '%s'\n", name, output, expected);
+      ierrors++;
+    }
+
+    text_sanitizer_free_output(output);
+  }
+}
+
+int main(void) {
+  /* Sparkle Heart, "w" and an invalid Byte */
+  const uint8_t sparkle_heart[] = {240, 159, 146, 150, 119, 250};
+  /* "Acción fallida" in UTF-8 */
+  const uint8_t spanish[] = "Acci\xc3\xb3n fallida";
+  /* "Minä" in UTF-8 */
+  const uint8_t finnish[] = "Min\xc3\xa4";
+  const char *finnish_map = "{\"fi\": {\"e4\": \"a\"}}";
+  const char *broken_map = "{\"e4\": 1}";
+
+  TextSanitizer *sanitizer = text_sanitizer_new();
+
+  if (sanitizer == NULL) {
+    fprintf(stderr, "text_sanitizer_new: no handle\n");
+    return 1;
+  }
+
+  check_code("add en", text_sanitizer_add_request_language(sanitizer, "en"), TEXT_SANITIZER_OK);
+  check_sanitize(sanitizer, "sparkle heart", sparkle_heart, sizeof(sparkle_heart), "<3w(?fa)");
+
+  check_code("add es", text_sanitizer_add_request_language(sanitizer, "es"), TEXT_SANITIZER_OK);
+  check_sanitize(sanitizer, "spanish", spanish, sizeof(spanish) - 1, "Accion fallida");
+
+  check_code("load fi",
+             text_sanitizer_load_conversion_map(sanitizer, (const uint8_t *)finnish_map,
+                                                strlen(finnish_map)),
+             TEXT_SANITIZER_OK);
+  check_code("load broken",
+             text_sanitizer_load_language_map(sanitizer, "xx", (const uint8_t *)broken_map,
+                                              strlen(broken_map)),
+             TEXT_SANITIZER_ERROR_MAP);
+  check_code("clear", text_sanitizer_clear_request_languages(sanitizer), TEXT_SANITIZER_OK);
+  check_code("add fi", text_sanitizer_add_request_language(sanitizer, "fi"), TEXT_SANITIZER_OK);
+  check_sanitize(sanitizer, "finnish", finnish, sizeof(finnish) - 1, "Mina");
+
+  check_code("null handle", text_sanitizer_add_request_language(NULL, "de"),
+             TEXT_SANITIZER_ERROR_NULL);
+
+  text_sanitizer_free(sanitizer);
+  text_sanitizer_free(NULL);
+
+  if (ierrors == 0) {
+    printf("C interface tests: ok\n");
+  }
+
+  return ierrors == 0 ? 0 : 1;
+}
@@ -0,0 +1,224 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_ffi
+* @subpackage tests/c_interface.rs
+
+* This module runs tests on the C interface of the Text-Sanitizer library
+* It compiles the C test program against the built library and runs it
+*
+*---------------------------------
+* Requirements:
+* - A C compiler must be installed as "cc" or set in the environment variable "CC"
+*/
+
+#[cfg(test)]
+mod c_interface {
+    use std::env;
+    use std::fs::read_to_string;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    //==============================================================================
+    // Auxiliary Functions
+
+    fn find_crate_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn find_library_dir() -> PathBuf {
+        // The test executable is located in "target/<profile>/deps"
+        let mut libdir = env::current_exe().expect("Module Path unknown!");
+
+        libdir.pop();
+
+        if libdir.ends_with("deps") {
+            libdir.pop();
+        }
+
+        libdir
+    }
+
+    /// Translates a Rust type of the C interface into its C declaration.
+    fn translate_type(srust: &str) -> String {
+        let mut vtokens = srust.trim().splitn(2, ' ');
+        let stoken = vtokens.next().unwrap_or("");
+        let starget = vtokens.next().unwrap_or("");
+
+        match stoken {
+            "*const" => format!("const {} *", translate_type(starget)),
+            "*mut" => format!("{} *", translate_type(starget)),
+            "" => String::from("void"),
+            "c_int" => String::from("int"),
+            "c_char" => String::from("char"),
+            "u8" => String::from("uint8_t"),
+            "usize" => String::from("size_t"),
+            _ => stoken.to_string(),
+        }
+    }
+
+    /// Builds the C prototypes of the functions in the ffi source
+    /// like "int text_sanitizer_clear_request_languages(TextSanitizer *sanitizer);"
+    fn list_exported_functions(sourcefile: &Path) -> Vec<String> {
+        let ssource = read_to_string(sourcefile).expect("ffi source not readable");
+        let mut vfunctions = Vec::new();
+        let mut ssearch = ssource.as_str();
+
+        while let Some(ifnpos) = ssearch.find("extern \"C\" fn ") {
+            let sfunction = &ssearch[(ifnpos + 14)..];
+            let iparpos = sfunction.find('(').expect("function without parameters");
+            let iendpos = sfunction.find(')').expect("function without parameters");
+            let ibodypos = sfunction.find('{').expect("function without body");
+            let vparameters: Vec<String> = sfunction[(iparpos + 1)..iendpos]
+                .split(',')
+                .filter(|sparameter| !sparameter.trim().is_empty())
+                .map(|sparameter| {
+                    let (sname, stype) = sparameter.split_at(sparameter.find(':').unwrap());
+
+                    format!("{} {}", translate_type(&stype[1..]), sname.trim())
+                })
+                .collect();
+            let ssignature = &sfunction[(iendpos + 1)..ibodypos];
+            let sreturn = match ssignature.find("->") {
+                Some(iretpos) => &ssignature[(iretpos + 2)..],
+                None => "",
+            };
+
+            vfunctions.push(format!(
+                "{} {}({});",
+                translate_type(sreturn),
+                &sfunction[..iparpos],
+                if vparameters.is_empty() {
+                    String::from("void")
+                } else {
+                    vparameters.join(", ")
+                }
+            ));
+
+            ssearch = &sfunction[ibodypos..];
+        }
+
+        vfunctions
+    }
+
+    /// Lists the error codes of the ffi source like "#define TEXT_SANITIZER_ERROR_NULL -1"
+    fn list_error_codes(sourcefile: &Path) -> Vec<String> {
+        let ssource = read_to_string(sourcefile).expect("ffi source not readable");
+
+        ssource
+            .lines()
+            .filter(|sline| sline.starts_with("pub const TEXT_SANITIZER_"))
+            .map(|sline| {
+                let sdeclaration = sline[10..].trim_end_matches(';');
+                let (sname, svalue) = sdeclaration.split_at(sdeclaration.find(':').unwrap());
+
+                format!(
+                    "#define {} {}",
+                    sname,
+                    svalue.split('=').nth(1).unwrap_or("").trim()
+                )
+            })
+            .collect()
+    }
+
+    /// Removes the comments and normalizes the whitespace of a C source.
+    fn normalize_c_source(ssource: &str) -> String {
+        let mut snormalized = String::with_capacity(ssource.len());
+        let mut ssearch = ssource;
+
+        while let Some(icmtpos) = ssearch.find("/*") {
+            snormalized.push_str(&ssearch[..icmtpos]);
+
+            ssearch = match ssearch[icmtpos..].find("*/") {
+                Some(iendpos) => &ssearch[(icmtpos + iendpos + 2)..],
+                None => "",
+            };
+        }
+
+        snormalized.push_str(ssearch);
+
+        normalize_declaration(&snormalized)
+    }
+
+    /// Collapses the whitespace and removes it around the pointer stars.
+    fn normalize_declaration(sdeclaration: &str) -> String {
+        let mut snormalized = String::with_capacity(sdeclaration.len());
+
+        for sword in sdeclaration.split_whitespace() {
+            if !snormalized.is_empty() {
+                snormalized.push(' ');
+            }
+
+            snormalized.push_str(sword);
+        }
+
+        snormalized
+            .replace("( ", "(")
+            .replace(" )", ")")
+            .replace(" *", "*")
+            .replace("* ", "*")
+    }
+
+    #[test]
+    fn header_declarations() {
+        let cratedir = find_crate_dir();
+        let sheader =
+            read_to_string(cratedir.join("include/text_sanitizer.h")).expect("header not readable");
+        let sheader = normalize_c_source(&sheader);
+        let vfunctions = list_exported_functions(&cratedir.join("src/ffi.rs"));
+        let vcodes = list_error_codes(&cratedir.join("src/ffi.rs"));
+
+        println!("ffi fns: '{:?}'", vfunctions);
+        println!("ffi codes: '{:?}'", vcodes);
+
+        assert_eq!(vfunctions.len(), 9);
+        assert_eq!(vcodes.len(), 6);
+
+        for sfunction in vfunctions.iter().chain(vcodes.iter()) {
+            assert!(
+                sheader.contains(&normalize_declaration(sfunction)),
+                "'{}' is not declared in the header",
+                sfunction
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn c_test_program() {
+        let cratedir = find_crate_dir();
+        let libdir = find_library_dir();
+        let program = libdir.join("test_text_sanitizer_c");
+        let scompiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+
+        println!("lib dir: '{:?}'", libdir);
+
+        let compile = Command::new(&scompiler)
+            .arg("-Wall")
+            .arg("-o")
+            .arg(&program)
+            .arg(cratedir.join("tests/c/test_text_sanitizer.c"))
+            .arg("-I")
+            .arg(cratedir.join("include"))
+            .arg("-L")
+            .arg(&libdir)
+            .arg(format!("-Wl,-rpath,{}", libdir.display()))
+            .arg("-ltext_sanitizer_ffi")
+            .output()
+            .expect("C compiler could not be started");
+
+        println!("cc out:\n{}", String::from_utf8_lossy(&compile.stdout));
+        println!("cc err:\n{}", String::from_utf8_lossy(&compile.stderr));
+
+        assert!(compile.status.success());
+
+        let run = Command::new(&program)
+            .output()
+            .expect("C test program could not be started");
+
+        println!("test out:\n{}", String::from_utf8_lossy(&run.stdout));
+        println!("test err:\n{}", String::from_utf8_lossy(&run.stderr));
+
+        assert!(run.status.success());
+    }
+}
@@ -0,0 +1,38 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_py
+* @subpackage lib.rs
+
+* This module provides the Python bindings for the Text-Sanitizer
+*
+*---------------------------------
+* Requirements:
+* - The Rust module "python" must be installed
+*/
+
+//! Python bindings to the Text-Sanitizer library
+//!
+//! The crate builds the native Python module `text_sanitizer`.
+//! The wheel is built with [`maturin`](https://www.maturin.rs):
+//!
+//! ```plain
+//! $ cd text-sanitizer_py
+//! $ maturin build --release
+//! $ pip install ../target/wheels/text_sanitizer-*.whl
+//! ```
+//!
+//! The module exposes the classes `TextSanitizer` and `ConversionMap`
+//! and the function `sanitize()`:
+//!
+//! ```python
+//! import text_sanitizer
+//!
+//! sanitizer = text_sanitizer.TextSanitizer(["es", "en"])
+//!
+//! print(sanitizer.sanitize(b"Acci\xc3\xb3n \xe2\x9d\xa4"))  # Accion <3
+//! ```
+
+pub mod python;
+
+pub use python::{PyConversionMap, PyTextSanitizer};
@@ -0,0 +1,316 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_py
+* @subpackage python.rs
+
+* This module implements the Python classes of the Text-Sanitizer library
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+* - The Rust crate "serde_json" must be installed
+* - The Rust crate "pyo3" must be installed
+*/
+
+// The "pyo3" macros convert the returned "PyResult" once more
+#![allow(clippy::useless_conversion)]
+
+extern crate pyo3;
+extern crate serde_json;
+extern crate text_sanitizer;
+
+use std::collections::HashMap;
+use std::fs;
+
+use pyo3::exceptions::{PyOSError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyString};
+
+use text_sanitizer::{ConversionMap, LanguageMap, TextSanitizer};
+
+//==============================================================================
+// Auxiliary Functions
+
+fn parse_json_map(data: &Bound<'_, PyAny>) -> PyResult<ConversionMap> {
+    let result = if let Ok(sdata) = data.downcast::<PyString>() {
+        serde_json::from_str::<ConversionMap>(sdata.to_str()?)
+    } else if let Ok(vdata) = data.downcast::<PyBytes>() {
+        serde_json::from_slice::<ConversionMap>(vdata.as_bytes())
+    } else {
+        return Err(PyTypeError::new_err("map data must be 'str' or 'bytes'"));
+    };
+
+    result.map_err(|e| PyValueError::new_err(format!("map data is not a valid map: {}", e)))
+}
+
+fn sanitize_any(sanitizer: &TextSanitizer, text: &Bound<'_, PyAny>) -> PyResult<String> {
+    let py = text.py();
+
+    if let Ok(stext) = text.downcast::<PyString>() {
+        let stext = stext.to_str()?;
+
+        Ok(py.allow_threads(|| sanitizer.sanitize_string(stext)))
+    } else if let Ok(vtext) = text.downcast::<PyBytes>() {
+        let vtext = vtext.as_bytes();
+
+        Ok(py.allow_threads(|| sanitizer.sanitize_u8(vtext)))
+    } else if let Ok(vtext) = text.downcast::<PyByteArray>() {
+        //A "bytearray" can change while the GIL is released
+        let vtext = vtext.to_vec();
+
+        Ok(py.allow_threads(|| sanitizer.sanitize_u8(&vtext)))
+    } else {
+        Err(PyTypeError::new_err(
+            "text must be 'str', 'bytes' or 'bytearray'",
+        ))
+    }
+}
+
+//==============================================================================
+// Structure PyConversionMap Declaration
+
+/// Python class `ConversionMap` holding custom `LanguageMap`s by language shortcode.\
+/// The replacements of a `LanguageMap` are indexed by the hexadecimal codepoint
+/// like `{"fi": {"e4": "a"}}`.
+#[pyclass(name = "ConversionMap", module = "text_sanitizer")]
+#[derive(Clone)]
+pub struct PyConversionMap {
+    _oconv_map: ConversionMap,
+}
+
+#[pymethods]
+impl PyConversionMap {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    #[new]
+    #[pyo3(signature = (maps = None))]
+    fn new(maps: Option<HashMap<String, HashMap<String, String>>>) -> Self {
+        let mut conv_map = ConversionMap(HashMap::new());
+
+        if let Some(maps) = maps {
+            for (slang, lngmap) in maps {
+                conv_map.0.insert(slang, LanguageMap(lngmap));
+            }
+        }
+
+        PyConversionMap {
+            _oconv_map: conv_map,
+        }
+    }
+
+    /// Parses a `ConversionMap` from JSON data given as `str` or `bytes`.
+    #[staticmethod]
+    fn from_json(data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyConversionMap {
+            _oconv_map: parse_json_map(data)?,
+        })
+    }
+
+    /// Reads a `ConversionMap` from a JSON file.
+    #[staticmethod]
+    fn from_file(path: &str) -> PyResult<Self> {
+        let vdata = fs::read(path)
+            .map_err(|e| PyOSError::new_err(format!("map file '{}': {}", path, e)))?;
+
+        serde_json::from_slice::<ConversionMap>(&vdata)
+            .map(|conv_map| PyConversionMap {
+                _oconv_map: conv_map,
+            })
+            .map_err(|e| PyValueError::new_err(format!("map file '{}': {}", path, e)))
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Adds or replaces the `LanguageMap` of one language.
+    fn set_language_map(&mut self, language: &str, map: HashMap<String, String>) {
+        self._oconv_map
+            .0
+            .insert(language.to_string(), LanguageMap(map));
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Lists the language shortcodes in alphabetical order.
+    fn languages(&self) -> Vec<String> {
+        let mut vlangs: Vec<String> = self._oconv_map.0.keys().cloned().collect();
+
+        vlangs.sort();
+
+        vlangs
+    }
+
+    fn __len__(&self) -> usize {
+        self._oconv_map.0.len()
+    }
+
+    fn __contains__(&self, language: &str) -> bool {
+        self._oconv_map.0.contains_key(language)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ConversionMap({:?})", self.languages())
+    }
+}
+
+//==============================================================================
+// Structure PyTextSanitizer Declaration
+
+/// Python class `TextSanitizer` wrapping the Rust `TextSanitizer`.\
+/// The requested languages are applied in the given order.
+/// The options string takes the same runtime options as the executable like `"-q -d"`.
+#[pyclass(name = "TextSanitizer", module = "text_sanitizer")]
+#[derive(Clone)]
+pub struct PyTextSanitizer {
+    _sanitizer: TextSanitizer,
+    _vrqlangs: Vec<String>,
+}
+
+#[pymethods]
+impl PyTextSanitizer {
+    /*----------------------------------------------------------------------------
+     * Constructors
+     */
+
+    #[new]
+    #[pyo3(signature = (languages = None, options = "", conversion_map = None))]
+    fn new(
+        languages: Option<Vec<String>>,
+        options: &str,
+        conversion_map: Option<PyRef<'_, PyConversionMap>>,
+    ) -> Self {
+        let mut sanitizer = PyTextSanitizer {
+            _sanitizer: TextSanitizer::new_with_options_string(options),
+            _vrqlangs: Vec::new(),
+        };
+
+        if let Some(conv_map) = conversion_map {
+            sanitizer.load_conversion_map(&conv_map);
+        }
+
+        if let Some(vlangs) = languages {
+            for slang in vlangs {
+                sanitizer.add_request_language(&slang);
+            }
+        }
+
+        sanitizer
+    }
+
+    /*----------------------------------------------------------------------------
+     * Administration Methods
+     */
+
+    /// Adds a language shortcode to the applied `LanguageMap`s.
+    fn add_request_language(&mut self, language: &str) {
+        self._sanitizer.add_request_language(language);
+
+        if !self._vrqlangs.iter().any(|slang| slang == language) {
+            self._vrqlangs.push(language.to_string());
+        }
+    }
+
+    /// Removes all requested languages.
+    fn clear_request_languages(&mut self) {
+        self._sanitizer.clear_request_languages();
+        self._vrqlangs.clear();
+    }
+
+    /// Adds or replaces the `LanguageMap`s of a `ConversionMap`.
+    /// The built-in `LanguageMap`s of other languages are kept.
+    fn load_conversion_map(&mut self, conversion_map: &PyConversionMap) {
+        for (slang, lngmap) in conversion_map._oconv_map.0.iter() {
+            self._sanitizer.set_language_map(slang, lngmap.clone());
+        }
+    }
+
+    /// Adds or replaces the `LanguageMap`s from JSON data given as `str` or `bytes`.
+    fn load_conversion_map_json(&mut self, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        for (slang, lngmap) in parse_json_map(data)?.0 {
+            self._sanitizer.set_language_map(&slang, lngmap);
+        }
+
+        Ok(())
+    }
+
+    /// Adds or replaces the `LanguageMap` of one language.
+    fn set_language_map(&mut self, language: &str, map: HashMap<String, String>) {
+        self._sanitizer.set_language_map(language, LanguageMap(map));
+    }
+
+    /// Removes the `LanguageMap` of one language.
+    fn delete_language_map(&mut self, language: &str) {
+        self._sanitizer.delete_language_map(language);
+    }
+
+    /*----------------------------------------------------------------------------
+     * Consultation Methods
+     */
+
+    /// Sanitizes a `str`, `bytes` or `bytearray` text and returns the plain ASCII `str`.\
+    /// Invalid UTF-8 sequences within `bytes` are reconstructed like in the executable.
+    fn sanitize(&self, text: &Bound<'_, PyAny>) -> PyResult<String> {
+        sanitize_any(&self._sanitizer, text)
+    }
+
+    #[getter]
+    fn request_languages(&self) -> Vec<String> {
+        self._vrqlangs.clone()
+    }
+
+    fn has_request_language(&self, language: &str) -> bool {
+        self._sanitizer.has_request_language(language)
+    }
+
+    #[getter]
+    fn quiet(&self) -> bool {
+        self._sanitizer.is_quiet()
+    }
+
+    #[getter]
+    fn debug(&self) -> bool {
+        self._sanitizer.is_debug()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TextSanitizer({:?})", self._vrqlangs)
+    }
+}
+
+//==============================================================================
+// Module Functions
+
+/// Sanitizes a text with the built-in `LanguageMap`s of the requested languages.
+#[pyfunction]
+#[pyo3(signature = (text, languages = None, options = ""))]
+fn sanitize(
+    text: &Bound<'_, PyAny>,
+    languages: Option<Vec<String>>,
+    options: &str,
+) -> PyResult<String> {
+    let mut sanitizer = TextSanitizer::new_with_options_string(options);
+
+    for slang in languages.unwrap_or_default() {
+        sanitizer.add_request_language(&slang);
+    }
+
+    sanitize_any(&sanitizer, text)
+}
+
+/// Native Python module `text_sanitizer`
+#[pymodule]
+#[pyo3(name = "text_sanitizer")]
+fn text_sanitizer_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    module.add_class::<PyTextSanitizer>()?;
+    module.add_class::<PyConversionMap>()?;
+    module.add_function(wrap_pyfunction!(sanitize, module)?)?;
+
+    Ok(())
+}
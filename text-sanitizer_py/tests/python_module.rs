@@ -0,0 +1,77 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_py
+* @subpackage tests/python_module.rs
+
+* This module runs the Python tests of the Text-Sanitizer bindings
+* It loads the built library as Python module "text_sanitizer"
+* The installed wheel is tested by running "tests/test_text_sanitizer.py" directly
+*
+*---------------------------------
+* Requirements:
+* - A Python 3 interpreter must be installed as "python3" or set
+*   in the environment variable "PYTHON"
+*/
+
+#[cfg(test)]
+mod python_module {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    //==============================================================================
+    // Auxiliary Functions
+
+    fn find_crate_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn find_library_dir() -> PathBuf {
+        // The test executable is located in "target/<profile>/deps"
+        let mut libdir = env::current_exe().expect("Module Path unknown!");
+
+        libdir.pop();
+
+        if libdir.ends_with("deps") {
+            libdir.pop();
+        }
+
+        libdir
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn python_tests() {
+        let cratedir = find_crate_dir();
+        let libdir = find_library_dir();
+        let moduledir = libdir.join("python");
+        let spython = env::var("PYTHON").unwrap_or_else(|_| String::from("python3"));
+
+        println!("lib dir: '{:?}'", libdir);
+
+        // Python finds the module only by its own name
+        fs::create_dir_all(&moduledir).expect("module directory not created");
+        fs::copy(
+            libdir.join("libtext_sanitizer_py.so"),
+            moduledir.join("text_sanitizer.so"),
+        )
+        .expect("library not copied");
+
+        let run = Command::new(&spython)
+            .arg("-m")
+            .arg("unittest")
+            .arg("-v")
+            .arg("test_text_sanitizer")
+            .current_dir(cratedir.join("tests"))
+            .env("PYTHONPATH", &moduledir)
+            .output()
+            .expect("Python interpreter could not be started");
+
+        println!("test out:\n{}", String::from_utf8_lossy(&run.stdout));
+        println!("test err:\n{}", String::from_utf8_lossy(&run.stderr));
+
+        assert!(run.status.success());
+    }
+}
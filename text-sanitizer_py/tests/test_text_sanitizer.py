@@ -0,0 +1,147 @@
+"""
+@author Bodo (Hugo) Barwich
+@version 2026-10-19
+@package text-sanitizer_py
+@subpackage tests/test_text_sanitizer.py
+
+This module runs tests on the Python bindings of the Text-Sanitizer library
+It processes the test files and checks the results against the result files
+
+---------------------------------
+Requirements:
+- The Python module "text_sanitizer" must be installed from the wheel
+  or be found in the "PYTHONPATH"
+"""
+
+import json
+import os
+import threading
+import unittest
+
+import text_sanitizer
+
+DATA_DIR = os.path.join(
+    os.path.dirname(os.path.abspath(__file__)), "..", "..", "tests", "data"
+)
+
+SPARKLE_HEART = bytes([240, 159, 146, 150, 119, 250])
+
+
+def list_testdata():
+    return sorted(
+        name
+        for name in os.listdir(DATA_DIR)
+        if not os.path.splitext(name)[0].endswith("_result")
+    )
+
+
+def read_file(name):
+    with open(os.path.join(DATA_DIR, name), "rb") as datafile:
+        return datafile.read()
+
+
+class TextSanitizerTests(unittest.TestCase):
+    def test_sanitize_bytes(self):
+        sanitizer = text_sanitizer.TextSanitizer(["en"])
+
+        self.assertEqual(sanitizer.sanitize(SPARKLE_HEART), "<3w(?fa)")
+        self.assertEqual(sanitizer.sanitize(bytearray(SPARKLE_HEART)), "<3w(?fa)")
+
+    def test_sanitize_str(self):
+        sanitizer = text_sanitizer.TextSanitizer(["es", "en"])
+
+        self.assertEqual(sanitizer.sanitize("Acción ❤"), "Accion <3")
+
+    def test_sanitize_type_error(self):
+        sanitizer = text_sanitizer.TextSanitizer(["en"])
+
+        with self.assertRaises(TypeError):
+            sanitizer.sanitize(42)
+
+    def test_request_languages(self):
+        sanitizer = text_sanitizer.TextSanitizer(["de"], options="-q")
+
+        sanitizer.add_request_language("en")
+        sanitizer.add_request_language("de")
+
+        self.assertEqual(sanitizer.request_languages, ["de", "en"])
+        self.assertTrue(sanitizer.has_request_language("en"))
+        self.assertTrue(sanitizer.quiet)
+        self.assertFalse(sanitizer.debug)
+
+        sanitizer.clear_request_languages()
+
+        self.assertEqual(sanitizer.request_languages, [])
+        self.assertFalse(sanitizer.has_request_language("en"))
+
+    def test_conversion_map(self):
+        conv_map = text_sanitizer.ConversionMap.from_json('{"fi": {"e4": "a"}}')
+
+        conv_map.set_language_map("custom", {"20ac": "EUR"})
+
+        self.assertEqual(conv_map.languages(), ["custom", "fi"])
+        self.assertEqual(len(conv_map), 2)
+        self.assertIn("fi", conv_map)
+
+        sanitizer = text_sanitizer.TextSanitizer(
+            ["fi", "custom", "de"], conversion_map=conv_map
+        )
+
+        self.assertEqual(sanitizer.sanitize("Häuser 5€"), "Hauser 5EUR")
+
+        sanitizer.load_conversion_map_json(json.dumps({"fi": {"e4": "ae"}}).encode())
+
+        self.assertEqual(sanitizer.sanitize("Häuser"), "Haeuser")
+
+    def test_conversion_map_error(self):
+        with self.assertRaises(ValueError):
+            text_sanitizer.ConversionMap.from_json("[1, 2]")
+
+        with self.assertRaises(TypeError):
+            text_sanitizer.ConversionMap.from_json(42)
+
+        with self.assertRaises(OSError):
+            text_sanitizer.ConversionMap.from_file(
+                os.path.join(DATA_DIR, "missing-map.json")
+            )
+
+    def test_module_sanitize(self):
+        self.assertEqual(
+            text_sanitizer.sanitize("Acción", ["es", "en"]), "Accion"
+        )
+
+    def test_files(self):
+        sanitizer = text_sanitizer.TextSanitizer(["en", "es", "de"])
+
+        for name in list_testdata():
+            basename, extension = os.path.splitext(name)
+
+            with self.subTest(file=name):
+                result = read_file(basename + "_result" + extension)
+
+                self.assertEqual(
+                    sanitizer.sanitize(read_file(name)), result.decode("utf-8")
+                )
+
+    def test_threads(self):
+        sanitizer = text_sanitizer.TextSanitizer(["en", "es", "de"])
+        text = read_file("lanzarote-com_de-ausfluge.html")
+        expected = sanitizer.sanitize(text)
+        results = []
+
+        def run():
+            results.append(sanitizer.sanitize(text))
+
+        threads = [threading.Thread(target=run) for _ in range(4)]
+
+        for thread in threads:
+            thread.start()
+
+        for thread in threads:
+            thread.join()
+
+        self.assertEqual(results, [expected] * 4)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,184 @@
+/*
+ * @author Bodo (Hugo) Barwich
+ * @version 2026-10-19
+ * @package text-sanitizer_wasm
+ * @subpackage js/text_sanitizer.mjs
+ *
+ * This module wraps the raw exports of the Text-Sanitizer WebAssembly module
+ * into a JavaScript friendly API
+ * The exports are implemented in "src/wasm.rs"
+ *
+ *---------------------------------
+ * Requirements:
+ * - A JavaScript runtime with "WebAssembly", "TextEncoder" and "TextDecoder"
+ *   like a current Browser or Node.js
+ */
+
+const TEXT_SANITIZER_OK = 0;
+
+const ERROR_MESSAGES = {
+  [-1]: "invalid sanitizer handle",
+  [-2]: "text is not valid UTF-8",
+  [-3]: "map is not a valid JSON map",
+};
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder("utf-8");
+
+function checkCode(code) {
+  if (code !== TEXT_SANITIZER_OK) {
+    throw new Error(ERROR_MESSAGES[code] || `error code ${code}`);
+  }
+}
+
+function toBytes(data) {
+  if (typeof data === "string") {
+    return encoder.encode(data);
+  }
+
+  if (data instanceof Uint8Array) {
+    return data;
+  }
+
+  if (data instanceof ArrayBuffer || ArrayBuffer.isView(data)) {
+    return new Uint8Array(data.buffer || data, data.byteOffset || 0, data.byteLength);
+  }
+
+  return encoder.encode(JSON.stringify(data));
+}
+
+/**
+ * Sanitizer which converts rich UTF-8 text into plain ASCII text.
+ * It is created with `TextSanitizerModule.createSanitizer()` and must be
+ * released with `free()`.
+ */
+export class TextSanitizer {
+  constructor(module, handle) {
+    this._module = module;
+    this._handle = handle;
+  }
+
+  /**
+   * Adds a language shortcode to the applied language maps.
+   * The order of the calls is the priority order of the languages.
+   */
+  addRequestLanguage(language) {
+    this._module._withBuffer(toBytes(language), (ptr, len) =>
+      checkCode(this._module._exports.text_sanitizer_add_request_language(this._handle, ptr, len))
+    );
+  }
+
+  /** Removes all requested languages. */
+  clearRequestLanguages() {
+    checkCode(this._module._exports.text_sanitizer_clear_request_languages(this._handle));
+  }
+
+  /**
+   * Adds or replaces the language map of one language.
+   * The map is a JSON string or an object of replacements by codepoint like `{"e4": "ae"}`.
+   */
+  setLanguageMap(language, map) {
+    this._module._withBuffer(toBytes(language), (langPtr, langLen) =>
+      this._module._withBuffer(toBytes(map), (mapPtr, mapLen) =>
+        checkCode(
+          this._module._exports.text_sanitizer_load_language_map(
+            this._handle,
+            langPtr,
+            langLen,
+            mapPtr,
+            mapLen
+          )
+        )
+      )
+    );
+  }
+
+  /**
+   * Adds or replaces several language maps at once.
+   * The map is a JSON string or an object of language maps by language shortcode
+   * like `{"fi": {"e4": "a"}}`.
+   */
+  loadConversionMap(map) {
+    this._module._withBuffer(toBytes(map), (ptr, len) =>
+      checkCode(this._module._exports.text_sanitizer_load_conversion_map(this._handle, ptr, len))
+    );
+  }
+
+  /**
+   * Sanitizes a string or the raw bytes of a `Uint8Array` and returns the plain ASCII string.
+   * Invalid UTF-8 sequences within the bytes are reconstructed like in the native build.
+   */
+  sanitize(text) {
+    const exports = this._module._exports;
+
+    return this._module._withBuffer(toBytes(text), (ptr, len) => {
+      checkCode(exports.text_sanitizer_sanitize(this._handle, ptr, len));
+
+      const outPtr = exports.text_sanitizer_output_ptr(this._handle);
+      const outLen = exports.text_sanitizer_output_len(this._handle);
+
+      return decoder.decode(new Uint8Array(exports.memory.buffer, outPtr, outLen));
+    });
+  }
+
+  /** Releases the sanitizer within the WebAssembly module. */
+  free() {
+    if (this._handle !== 0) {
+      this._module._exports.text_sanitizer_free(this._handle);
+      this._handle = 0;
+    }
+  }
+}
+
+/** Instance of the Text-Sanitizer WebAssembly module. */
+export class TextSanitizerModule {
+  constructor(instance) {
+    this._exports = instance.exports;
+  }
+
+  /** Creates a new `TextSanitizer` with the built-in language maps for the given languages. */
+  createSanitizer(languages = []) {
+    const sanitizer = new TextSanitizer(this, this._exports.text_sanitizer_new());
+
+    for (const language of languages) {
+      sanitizer.addRequestLanguage(language);
+    }
+
+    return sanitizer;
+  }
+
+  _withBuffer(bytes, callback) {
+    const len = bytes.length;
+    const ptr = this._exports.text_sanitizer_alloc(len);
+
+    try {
+      // The memory can grow on allocation so the view is created afterwards
+      new Uint8Array(this._exports.memory.buffer, ptr, len).set(bytes);
+
+      return callback(ptr, len);
+    } finally {
+      this._exports.text_sanitizer_dealloc(ptr, len);
+    }
+  }
+}
+
+/**
+ * Loads the WebAssembly module from a `Response`, an `ArrayBuffer`, a `Uint8Array`
+ * or a compiled `WebAssembly.Module`.
+ */
+export async function loadTextSanitizer(source) {
+  let instance;
+
+  if (source instanceof WebAssembly.Module) {
+    instance = await WebAssembly.instantiate(source, {});
+  } else {
+    const bytes =
+      typeof Response !== "undefined" && source instanceof Response
+        ? await source.arrayBuffer()
+        : source;
+
+    instance = (await WebAssembly.instantiate(bytes, {})).instance;
+  }
+
+  return new TextSanitizerModule(instance);
+}
@@ -0,0 +1,41 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_wasm
+* @subpackage lib.rs
+
+* This module provides the WebAssembly module for the Text-Sanitizer
+*
+*---------------------------------
+* Requirements:
+* - The Rust module "wasm" must be installed
+*/
+
+//! WebAssembly module of the Text-Sanitizer library
+//!
+//! The crate builds a WebAssembly module without any imports:
+//!
+//! ```plain
+//! $ cargo build --release --target wasm32-unknown-unknown --package text-sanitizer_wasm
+//! ```
+//!
+//! The JavaScript module `js/text_sanitizer.mjs` wraps the raw exports into
+//! a `TextSanitizer` class which takes a `Uint8Array` or a string and returns a string:
+//!
+//! ```javascript
+//! import { loadTextSanitizer } from "./text_sanitizer.mjs";
+//!
+//! const module = await loadTextSanitizer(await fetch("text_sanitizer_wasm.wasm"));
+//! const sanitizer = module.createSanitizer(["es", "en"]);
+//!
+//! console.log(sanitizer.sanitize("Acción ❤")); // Accion <3
+//!
+//! sanitizer.free();
+//! ```
+//!
+//! The library is built without the cargo feature `std`.
+//! So debug messages are never printed to STDOUT.
+
+pub mod wasm;
+
+pub use wasm::*;
@@ -0,0 +1,325 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_wasm
+* @subpackage wasm.rs
+
+* This module implements the raw WebAssembly exports of the Text-Sanitizer library
+* The JavaScript module "js/text_sanitizer.mjs" must match these functions
+*
+*---------------------------------
+* Requirements:
+* - The Rust crate "text-sanitizer" must be installed
+* - The Rust crate "serde_json" must be installed
+*/
+
+extern crate serde_json;
+extern crate text_sanitizer;
+
+use std::mem;
+use std::slice;
+use std::str;
+
+use text_sanitizer::{ConversionMap, LanguageMap, TextSanitizer};
+
+//==============================================================================
+// Constants
+
+/// The operation was successful
+pub const TEXT_SANITIZER_OK: i32 = 0;
+/// A required handle was `NULL`
+pub const TEXT_SANITIZER_ERROR_NULL: i32 = -1;
+/// A string argument was not valid UTF-8
+pub const TEXT_SANITIZER_ERROR_ENCODING: i32 = -2;
+/// The map buffer could not be parsed as JSON map
+pub const TEXT_SANITIZER_ERROR_MAP: i32 = -3;
+
+//==============================================================================
+// Structure WasmSanitizer Declaration
+
+/// Handle of a `TextSanitizer` which keeps the output of the last sanitizing
+/// until JavaScript has copied it out of the WebAssembly memory.
+#[derive(Debug)]
+pub struct WasmSanitizer {
+    _sanitizer: TextSanitizer,
+    _soutput: String,
+}
+
+//==============================================================================
+// Auxiliary Functions
+
+unsafe fn parse_buffer<'a>(buffer: *const u8, buffer_len: usize) -> &'a [u8] {
+    if buffer.is_null() || buffer_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(buffer, buffer_len)
+    }
+}
+
+unsafe fn parse_str<'a>(buffer: *const u8, buffer_len: usize) -> Result<&'a str, i32> {
+    str::from_utf8(parse_buffer(buffer, buffer_len)).map_err(|_| TEXT_SANITIZER_ERROR_ENCODING)
+}
+
+//==============================================================================
+// WebAssembly Interface
+
+/// Reserves `len` bytes in the WebAssembly memory to pass data into the module.
+/// The buffer must be released with `text_sanitizer_dealloc()`.
+#[no_mangle]
+pub extern "C" fn text_sanitizer_alloc(len: usize) -> *mut u8 {
+    let mut vbuffer: Vec<u8> = Vec::with_capacity(len);
+    let pbuffer = vbuffer.as_mut_ptr();
+
+    mem::forget(vbuffer);
+
+    pbuffer
+}
+
+/// Releases a buffer reserved with `text_sanitizer_alloc()`. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `buffer` must be `NULL` or returned by `text_sanitizer_alloc()` with the same `len`
+/// and not released before.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_dealloc(buffer: *mut u8, len: usize) {
+    if !buffer.is_null() {
+        drop(Vec::from_raw_parts(buffer, 0, len));
+    }
+}
+
+/// Creates a new `TextSanitizer` handle with the built-in `LanguageMap`s.\
+/// No language is requested yet.
+/// The returned handle must be released with `text_sanitizer_free()`.
+#[no_mangle]
+pub extern "C" fn text_sanitizer_new() -> *mut WasmSanitizer {
+    Box::into_raw(Box::new(WasmSanitizer {
+        _sanitizer: TextSanitizer::new(),
+        _soutput: String::new(),
+    }))
+}
+
+/// Releases a `TextSanitizer` handle. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `sanitizer` must be `NULL` or a handle created by this module
+/// which was not released before.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_free(sanitizer: *mut WasmSanitizer) {
+    if !sanitizer.is_null() {
+        drop(Box::from_raw(sanitizer));
+    }
+}
+
+/// Adds a language shortcode to the applied `LanguageMap`s.
+/// The order of the calls is the priority order of the languages.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle and `language` must point to
+/// at least `language_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_add_request_language(
+    sanitizer: *mut WasmSanitizer,
+    language: *const u8,
+    language_len: usize,
+) -> i32 {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    match parse_str(language, language_len) {
+        Ok(slang) => {
+            (*sanitizer)._sanitizer.add_request_language(slang);
+
+            TEXT_SANITIZER_OK
+        }
+        Err(ierr) => ierr,
+    }
+}
+
+/// Removes all requested languages.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_clear_request_languages(
+    sanitizer: *mut WasmSanitizer,
+) -> i32 {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    (*sanitizer)._sanitizer.clear_request_languages();
+
+    TEXT_SANITIZER_OK
+}
+
+/// Adds or replaces the `LanguageMap` of one language.\
+/// The buffer holds a JSON object of the replacements by codepoint like
+/// `{"e4": "ae", "fc": "ue"}`.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle, `language` must point to at least `language_len`
+/// bytes and `map` must point to at least `map_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_load_language_map(
+    sanitizer: *mut WasmSanitizer,
+    language: *const u8,
+    language_len: usize,
+    map: *const u8,
+    map_len: usize,
+) -> i32 {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    let slang = match parse_str(language, language_len) {
+        Ok(s) => s,
+        Err(ierr) => return ierr,
+    };
+
+    match serde_json::from_slice::<LanguageMap>(parse_buffer(map, map_len)) {
+        Ok(lngmap) => {
+            (*sanitizer)._sanitizer.set_language_map(slang, lngmap);
+
+            TEXT_SANITIZER_OK
+        }
+        Err(_) => TEXT_SANITIZER_ERROR_MAP,
+    }
+}
+
+/// Adds or replaces several `LanguageMap`s at once.\
+/// The buffer holds a JSON object of the `LanguageMap`s by language shortcode like
+/// `{"fi": {"e4": "a"}, "de": {"fc": "ue"}}`.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle and `map` must point to at least `map_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_load_conversion_map(
+    sanitizer: *mut WasmSanitizer,
+    map: *const u8,
+    map_len: usize,
+) -> i32 {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    match serde_json::from_slice::<ConversionMap>(parse_buffer(map, map_len)) {
+        Ok(conv_map) => {
+            for (slang, lngmap) in conv_map.0 {
+                (*sanitizer)._sanitizer.set_language_map(&slang, lngmap);
+            }
+
+            TEXT_SANITIZER_OK
+        }
+        Err(_) => TEXT_SANITIZER_ERROR_MAP,
+    }
+}
+
+/// Sanitizes a buffer of raw text bytes.\
+/// The output is kept in the handle and can be read with `text_sanitizer_output_ptr()`
+/// and `text_sanitizer_output_len()` until the next call.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle and `text` must point to at least `text_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_sanitize(
+    sanitizer: *mut WasmSanitizer,
+    text: *const u8,
+    text_len: usize,
+) -> i32 {
+    if sanitizer.is_null() {
+        return TEXT_SANITIZER_ERROR_NULL;
+    }
+
+    let handle = &mut *sanitizer;
+
+    handle._soutput = handle._sanitizer.sanitize_u8(parse_buffer(text, text_len));
+
+    TEXT_SANITIZER_OK
+}
+
+/// Returns the position of the last output in the WebAssembly memory.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_output_ptr(sanitizer: *const WasmSanitizer) -> *const u8 {
+    if sanitizer.is_null() {
+        return std::ptr::null();
+    }
+
+    let handle = &*sanitizer;
+
+    handle._soutput.as_ptr()
+}
+
+/// Returns the length of the last output in bytes.
+///
+/// # Safety
+///
+/// `sanitizer` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn text_sanitizer_output_len(sanitizer: *const WasmSanitizer) -> usize {
+    if sanitizer.is_null() {
+        return 0;
+    }
+
+    let handle = &*sanitizer;
+
+    handle._soutput.len()
+}
+
+//==============================================================================
+// Unit Tests
+
+#[test]
+fn wasm_sanitize_buffer() {
+    let vsparkle_heart = [240, 159, 146, 150, 119, 250];
+    let sfinnish_map = b"{\"fi\": {\"e4\": \"a\"}}";
+
+    unsafe {
+        let sanitizer = text_sanitizer_new();
+
+        assert_eq!(
+            text_sanitizer_add_request_language(sanitizer, b"en".as_ptr(), 2),
+            TEXT_SANITIZER_OK
+        );
+        assert_eq!(
+            text_sanitizer_add_request_language(sanitizer, [0xc3u8].as_ptr(), 1),
+            TEXT_SANITIZER_ERROR_ENCODING
+        );
+        assert_eq!(
+            text_sanitizer_load_conversion_map(
+                sanitizer,
+                sfinnish_map.as_ptr(),
+                sfinnish_map.len()
+            ),
+            TEXT_SANITIZER_OK
+        );
+        assert_eq!(
+            text_sanitizer_load_language_map(sanitizer, b"xx".as_ptr(), 2, b"[1]".as_ptr(), 3),
+            TEXT_SANITIZER_ERROR_MAP
+        );
+        assert_eq!(
+            text_sanitizer_sanitize(sanitizer, vsparkle_heart.as_ptr(), vsparkle_heart.len()),
+            TEXT_SANITIZER_OK
+        );
+
+        let voutput = slice::from_raw_parts(
+            text_sanitizer_output_ptr(sanitizer),
+            text_sanitizer_output_len(sanitizer),
+        );
+
+        assert_eq!(voutput, b"<3w(?fa)");
+
+        text_sanitizer_free(sanitizer);
+    }
+}
@@ -0,0 +1,83 @@
+/*
+ * @author Bodo (Hugo) Barwich
+ * @version 2026-10-19
+ * @package text-sanitizer_wasm
+ * @subpackage tests/node/test_text_sanitizer.mjs
+ *
+ * This module runs tests on the Text-Sanitizer WebAssembly module
+ * It processes the test files and checks the results against the native results
+ * It is run by the test "tests/node_module.rs"
+ *
+ *---------------------------------
+ * Requirements:
+ * - Node.js 18 or newer
+ * - The environment variable "WASM_MODULE" must point to the built WebAssembly module
+ * - The environment variable "NATIVE_DIR" must point to the results of the native build
+ */
+
+import assert from "node:assert/strict";
+import { readFileSync, readdirSync } from "node:fs";
+import { dirname, extname, join } from "node:path";
+import { test } from "node:test";
+import { fileURLToPath } from "node:url";
+
+import { loadTextSanitizer } from "../../js/text_sanitizer.mjs";
+
+const DATA_DIR = join(dirname(fileURLToPath(import.meta.url)), "../../../tests/data");
+
+const SPARKLE_HEART = new Uint8Array([240, 159, 146, 150, 119, 250]);
+
+const module = await loadTextSanitizer(readFileSync(process.env.WASM_MODULE));
+
+function listTestdata() {
+  return readdirSync(DATA_DIR)
+    .filter((name) => !name.slice(0, -extname(name).length).endsWith("_result"))
+    .sort();
+}
+
+test("sanitize Uint8Array", () => {
+  const sanitizer = module.createSanitizer(["en"]);
+
+  assert.equal(sanitizer.sanitize(SPARKLE_HEART), "<3w(?fa)");
+
+  sanitizer.free();
+});
+
+test("sanitize string", () => {
+  const sanitizer = module.createSanitizer(["es", "en"]);
+
+  assert.equal(sanitizer.sanitize("Acción ❤"), "Accion <3");
+  assert.equal(sanitizer.sanitize(""), "");
+
+  sanitizer.free();
+});
+
+test("load maps from JSON", () => {
+  const sanitizer = module.createSanitizer(["fi", "custom", "de"]);
+
+  sanitizer.loadConversionMap('{"fi": {"e4": "a"}}');
+  sanitizer.setLanguageMap("custom", { "20ac": "EUR" });
+
+  assert.equal(sanitizer.sanitize("Häuser 5€"), "Hauser 5EUR");
+
+  assert.throws(() => sanitizer.loadConversionMap("[1, 2]"), /not a valid JSON map/);
+
+  sanitizer.clearRequestLanguages();
+  sanitizer.addRequestLanguage("de");
+
+  assert.equal(sanitizer.sanitize("Häuser"), "Haeuser");
+
+  sanitizer.free();
+});
+
+test("files match the native build", () => {
+  const sanitizer = module.createSanitizer(["en", "es", "de"]);
+
+  for (const name of listTestdata()) {
+    const expected = readFileSync(join(process.env.NATIVE_DIR, name), "utf8");
+
+    assert.equal(sanitizer.sanitize(readFileSync(join(DATA_DIR, name))), expected, name);
+  }
+
+  sanitizer.free();
+});
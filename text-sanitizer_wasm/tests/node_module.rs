@@ -0,0 +1,97 @@
+/*
+* @author Bodo (Hugo) Barwich
+* @version 2026-10-19
+* @package text-sanitizer_wasm
+* @subpackage tests/node_module.rs
+
+* This module runs the Node.js tests of the Text-Sanitizer WebAssembly module
+* It builds the WebAssembly module, writes the results of the native build
+* and compares them with the results of the WebAssembly module
+*
+*---------------------------------
+* Requirements:
+* - The Rust target "wasm32-unknown-unknown" must be installed
+* - Node.js must be installed as "node" or set in the environment variable "NODE"
+*/
+
+#[cfg(test)]
+mod node_module {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    extern crate text_sanitizer;
+
+    use text_sanitizer::TextSanitizer;
+
+    //==============================================================================
+    // Auxiliary Functions
+
+    fn find_crate_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    fn write_native_results(datadir: &PathBuf, nativedir: &PathBuf) {
+        let mut sanitizer = TextSanitizer::new();
+
+        sanitizer.add_request_language("en");
+        sanitizer.add_request_language("es");
+        sanitizer.add_request_language("de");
+
+        fs::create_dir_all(nativedir).expect("native directory not created");
+
+        for entry in fs::read_dir(datadir).expect("test data not readable") {
+            let datafile = entry.expect("test data not readable").path();
+            let vtext = fs::read(&datafile).expect("test file not readable");
+
+            if let Some(name) = datafile.file_name() {
+                fs::write(nativedir.join(name), sanitizer.sanitize_u8(&vtext))
+                    .expect("native result not written");
+            }
+        }
+    }
+
+    #[test]
+    fn node_tests() {
+        let cratedir = find_crate_dir();
+        let wasmdir = cratedir.join("../target/wasm");
+        let nativedir = wasmdir.join("native");
+        let snode = env::var("NODE").unwrap_or_else(|_| String::from("node"));
+        let scargo = env::var("CARGO").unwrap_or_else(|_| String::from("cargo"));
+
+        // A separate target directory does not wait for the lock of the running build
+        let build = Command::new(&scargo)
+            .arg("build")
+            .arg("--package")
+            .arg("text-sanitizer_wasm")
+            .arg("--target")
+            .arg("wasm32-unknown-unknown")
+            .arg("--target-dir")
+            .arg(&wasmdir)
+            .output()
+            .expect("cargo could not be started");
+
+        println!("build err:\n{}", String::from_utf8_lossy(&build.stderr));
+
+        assert!(build.status.success());
+
+        write_native_results(&cratedir.join("../tests/data"), &nativedir);
+
+        let run = Command::new(&snode)
+            .arg("--test")
+            .arg(cratedir.join("tests/node/test_text_sanitizer.mjs"))
+            .env(
+                "WASM_MODULE",
+                wasmdir.join("wasm32-unknown-unknown/debug/text_sanitizer_wasm.wasm"),
+            )
+            .env("NATIVE_DIR", &nativedir)
+            .output()
+            .expect("Node.js could not be started");
+
+        println!("test out:\n{}", String::from_utf8_lossy(&run.stdout));
+        println!("test err:\n{}", String::from_utf8_lossy(&run.stderr));
+
+        assert!(run.status.success());
+    }
+}